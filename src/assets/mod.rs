@@ -5,4 +5,4 @@
 //! - resource caching and reference counting
 //! - hot-reloading assets in development
 
-
+pub mod processing;
@@ -0,0 +1,36 @@
+/// Makes every RGBA8 pixel whose color is within `tolerance` of `key` on
+/// each channel fully transparent, for sprite sheets that use a background
+/// color (classically magenta) instead of an alpha channel. Other pixels
+/// are left untouched.
+pub fn apply_color_key(pixels: &mut [u8], key: [u8; 3], tolerance: u8) {
+    for pixel in pixels.chunks_exact_mut(4) {
+        let matches = pixel[..3]
+            .iter()
+            .zip(key)
+            .all(|(&channel, key)| channel.abs_diff(key) <= tolerance);
+        if matches {
+            pixel[3] = 0;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keyed_pixels_become_transparent() {
+        let mut pixels = [
+            255, 0, 255, 255, // exact magenta
+            250, 4, 251, 200, // within tolerance
+            255, 0, 240, 255, // blue channel too far off
+            10, 20, 30, 128, // unrelated color
+        ];
+        apply_color_key(&mut pixels, [255, 0, 255], 5);
+
+        assert_eq!(
+            pixels,
+            [255, 0, 255, 0, 250, 4, 251, 0, 255, 0, 240, 255, 10, 20, 30, 128]
+        );
+    }
+}
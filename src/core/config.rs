@@ -0,0 +1,57 @@
+/// Startup settings for the engine.
+#[derive(Debug, Clone)]
+pub struct EngineConfig {
+    pub window_title: String,
+    /// Initial inner size of the window in logical pixels.
+    pub window_width: u32,
+    pub window_height: u32,
+    /// When false, `Immediate` is tried before `present_modes` so frames are
+    /// shown as soon as they're ready, at the cost of tearing.
+    pub vsync: bool,
+    /// Present modes to try, most preferred first. The first one the surface
+    /// supports is used; `Fifo` is the fallback since every surface has it.
+    /// `Immediate` can tear, so it is only used if listed here or when
+    /// `vsync` is off.
+    pub present_modes: Vec<wgpu::PresentMode>,
+    pub clear_color: wgpu::Color,
+    /// Seconds per fixed update. Must be positive.
+    pub fixed_timestep: f32,
+    /// Fixed updates allowed in one frame before the backlog is dropped.
+    pub max_fixed_steps: u32,
+    /// Frame rate cap, on top of any limit vsync already imposes. `None`
+    /// renders as fast as the present mode allows.
+    pub max_fps: Option<u32>,
+}
+
+impl EngineConfig {
+    /// Present modes to try in order, taking `vsync` into account.
+    pub fn preferred_present_modes(&self) -> Vec<wgpu::PresentMode> {
+        let mut modes = Vec::with_capacity(self.present_modes.len() + 1);
+        if !self.vsync {
+            modes.push(wgpu::PresentMode::Immediate);
+        }
+        modes.extend_from_slice(&self.present_modes);
+        modes
+    }
+}
+
+impl Default for EngineConfig {
+    fn default() -> Self {
+        Self {
+            window_title: "GreyEngine".to_string(),
+            window_width: 1280,
+            window_height: 720,
+            vsync: true,
+            present_modes: vec![wgpu::PresentMode::Mailbox, wgpu::PresentMode::Fifo],
+            clear_color: wgpu::Color {
+                r: 0.1,
+                g: 0.2,
+                b: 0.3,
+                a: 1.0,
+            },
+            fixed_timestep: 1.0 / 60.0,
+            max_fixed_steps: 5,
+            max_fps: None,
+        }
+    }
+}
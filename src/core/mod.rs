@@ -6,4 +6,8 @@
 //! - configuration and logging
 //! - the main game loop orchestration
 
-
+pub mod config;
+pub mod pool;
+pub mod profiler;
+pub mod state_machine;
+pub mod time;
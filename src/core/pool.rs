@@ -0,0 +1,94 @@
+/// Recycles objects so high-turnover things like particles don't allocate
+/// every frame.
+///
+/// Released objects are handed back out as-is on the next
+/// [`Pool::acquire`], so callers are responsible for resetting any state
+/// they care about. When the pool runs dry it grows by creating a fresh
+/// `T::default()`.
+pub struct Pool<T> {
+    free: Vec<T>,
+    created: usize,
+}
+
+impl<T: Default> Pool<T> {
+    pub fn new() -> Self {
+        Self {
+            free: Vec::new(),
+            created: 0,
+        }
+    }
+
+    /// Creates a pool with `capacity` objects ready to acquire.
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            free: (0..capacity).map(|_| T::default()).collect(),
+            created: capacity,
+        }
+    }
+
+    /// Takes an object from the pool, creating one if none are free.
+    pub fn acquire(&mut self) -> T {
+        self.free.pop().unwrap_or_else(|| {
+            self.created += 1;
+            T::default()
+        })
+    }
+}
+
+impl<T> Pool<T> {
+    /// Returns an object to the pool for a later [`Pool::acquire`].
+    pub fn release(&mut self, item: T) {
+        self.free.push(item);
+    }
+
+    /// Number of objects waiting to be acquired.
+    pub fn available(&self) -> usize {
+        self.free.len()
+    }
+
+    /// Total number of objects this pool has created.
+    pub fn created(&self) -> usize {
+        self.created
+    }
+}
+
+impl<T: Default> Default for Pool<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn released_objects_are_reused() {
+        let mut pool = Pool::<Vec<u32>>::new();
+        let mut particle = pool.acquire();
+        particle.push(7);
+        pool.release(particle);
+
+        assert_eq!(pool.acquire(), vec![7]);
+        assert_eq!(pool.created(), 1);
+        assert_eq!(pool.available(), 0);
+    }
+
+    #[test]
+    fn acquiring_past_capacity_grows_the_pool() {
+        let mut pool = Pool::<u32>::with_capacity(2);
+        assert_eq!(pool.available(), 2);
+        assert_eq!(pool.created(), 2);
+
+        let items: Vec<_> = (0..3).map(|_| pool.acquire()).collect();
+        assert_eq!(pool.created(), 3);
+        assert_eq!(pool.available(), 0);
+
+        for item in items {
+            pool.release(item);
+        }
+        assert_eq!(pool.available(), 3);
+        pool.acquire();
+        assert_eq!(pool.created(), 3);
+    }
+}
@@ -0,0 +1,123 @@
+use std::{
+    cell::RefCell,
+    time::{Duration, Instant},
+};
+
+/// Accumulated timings for one named scope over a frame.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScopeReport {
+    pub name: &'static str,
+    /// Nesting level the scope was first opened at (0 = outermost).
+    pub depth: usize,
+    pub calls: u32,
+    pub total: Duration,
+}
+
+#[derive(Default)]
+struct FrameRecords {
+    scopes: Vec<ScopeReport>,
+    depth: usize,
+}
+
+/// Lightweight frame profiler built on named scopes.
+///
+/// Open a scope with [`Profiler::scope`], keep the guard alive for the code
+/// being measured, and call [`Profiler::end_frame`] once per frame. Scopes
+/// opened several times in one frame are summed under one entry. Entries are
+/// kept in the order they were first opened, so an outer scope is always
+/// listed before the scopes nested inside it.
+#[derive(Default)]
+pub struct Profiler {
+    current: RefCell<FrameRecords>,
+    report: Vec<ScopeReport>,
+}
+
+impl Profiler {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Starts timing `name`; the time is recorded when the guard is dropped.
+    pub fn scope(&self, name: &'static str) -> ProfileScope<'_> {
+        let mut current = self.current.borrow_mut();
+        let depth = current.depth;
+        let index = match current.scopes.iter().position(|scope| scope.name == name) {
+            Some(index) => index,
+            None => {
+                current.scopes.push(ScopeReport {
+                    name,
+                    depth,
+                    calls: 0,
+                    total: Duration::ZERO,
+                });
+                current.scopes.len() - 1
+            }
+        };
+        current.depth += 1;
+
+        ProfileScope {
+            profiler: self,
+            index,
+            start: Instant::now(),
+        }
+    }
+
+    /// Publishes this frame's scopes to [`Profiler::report`] and starts a new frame.
+    pub fn end_frame(&mut self) {
+        let current = self.current.get_mut();
+        self.report = std::mem::take(&mut current.scopes);
+        current.depth = 0;
+    }
+
+    /// Timings from the last completed frame.
+    pub fn report(&self) -> &[ScopeReport] {
+        &self.report
+    }
+}
+
+/// Guard returned by [`Profiler::scope`].
+pub struct ProfileScope<'a> {
+    profiler: &'a Profiler,
+    index: usize,
+    start: Instant,
+}
+
+impl Drop for ProfileScope<'_> {
+    fn drop(&mut self) {
+        let elapsed = self.start.elapsed();
+        let mut current = self.profiler.current.borrow_mut();
+        current.depth = current.depth.saturating_sub(1);
+        let scope = &mut current.scopes[self.index];
+        scope.calls += 1;
+        scope.total += elapsed;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn nested_scopes_are_reported_outer_first() {
+        let mut profiler = Profiler::new();
+        {
+            let _outer = profiler.scope("update");
+            let _inner = profiler.scope("physics");
+            std::thread::sleep(Duration::from_millis(2));
+        }
+        assert!(profiler.report().is_empty());
+        profiler.end_frame();
+
+        let report = profiler.report();
+        let names: Vec<_> = report.iter().map(|scope| (scope.name, scope.depth)).collect();
+        assert_eq!(names, [("update", 0), ("physics", 1)]);
+        for scope in report {
+            assert_eq!(scope.calls, 1);
+            assert!(scope.total > Duration::ZERO);
+        }
+        assert!(report[0].total >= report[1].total);
+
+        profiler.end_frame();
+        assert!(profiler.report().is_empty());
+    }
+}
@@ -0,0 +1,198 @@
+type Callback<S> = Box<dyn FnMut(&S)>;
+
+/// Tracks which of a set of game states (menu, playing, game over, ...) is
+/// active, with optional callbacks fired on every transition.
+pub struct StateMachine<S> {
+    current: S,
+    on_enter: Option<Callback<S>>,
+    on_exit: Option<Callback<S>>,
+}
+
+impl<S> StateMachine<S> {
+    /// Starts in `initial`. No enter callback fires for it.
+    pub fn new(initial: S) -> Self {
+        Self {
+            current: initial,
+            on_enter: None,
+            on_exit: None,
+        }
+    }
+
+    pub fn current(&self) -> &S {
+        &self.current
+    }
+
+    /// Called with the new state after each transition.
+    pub fn set_on_enter(&mut self, f: impl FnMut(&S) + 'static) {
+        self.on_enter = Some(Box::new(f));
+    }
+
+    /// Called with the old state before each transition.
+    pub fn set_on_exit(&mut self, f: impl FnMut(&S) + 'static) {
+        self.on_exit = Some(Box::new(f));
+    }
+
+    /// Switches to `next`, firing exit for the current state and then
+    /// enter for `next`. Returns the state that was left.
+    pub fn transition_to(&mut self, next: S) -> S {
+        if let Some(on_exit) = &mut self.on_exit {
+            on_exit(&self.current);
+        }
+        let previous = std::mem::replace(&mut self.current, next);
+        if let Some(on_enter) = &mut self.on_enter {
+            on_enter(&self.current);
+        }
+        previous
+    }
+}
+
+/// A [`StateMachine`] that can stack states, e.g. a pause menu pushed over
+/// gameplay and popped to resume it. The bottom state is never popped, so
+/// there is always a current state.
+pub struct StateStack<S> {
+    states: Vec<S>,
+    on_enter: Option<Callback<S>>,
+    on_exit: Option<Callback<S>>,
+}
+
+impl<S> StateStack<S> {
+    /// Starts with `initial` as the only state. No enter callback fires
+    /// for it.
+    pub fn new(initial: S) -> Self {
+        Self {
+            states: vec![initial],
+            on_enter: None,
+            on_exit: None,
+        }
+    }
+
+    /// The top of the stack.
+    pub fn current(&self) -> &S {
+        self.states.last().expect("state stack is never empty")
+    }
+
+    pub fn depth(&self) -> usize {
+        self.states.len()
+    }
+
+    /// Called with a state when it is pushed or transitioned to.
+    pub fn set_on_enter(&mut self, f: impl FnMut(&S) + 'static) {
+        self.on_enter = Some(Box::new(f));
+    }
+
+    /// Called with a state when it is popped or transitioned away from.
+    pub fn set_on_exit(&mut self, f: impl FnMut(&S) + 'static) {
+        self.on_exit = Some(Box::new(f));
+    }
+
+    /// Puts `state` on top of the current one, which stays on the stack
+    /// untouched until `state` is popped.
+    pub fn push(&mut self, state: S) {
+        self.states.push(state);
+        if let Some(on_enter) = &mut self.on_enter {
+            on_enter(self.states.last().expect("state stack is never empty"));
+        }
+    }
+
+    /// Removes the top state, firing exit for it, and returns to the one
+    /// below. The state below is resumed rather than entered: just as
+    /// [`StateStack::push`] fires no exit for it, no enter fires when it
+    /// becomes current again. Returns `None` without firing callbacks if
+    /// only the bottom state is left.
+    pub fn pop(&mut self) -> Option<S> {
+        if self.states.len() <= 1 {
+            return None;
+        }
+        let top = self.states.pop()?;
+        if let Some(on_exit) = &mut self.on_exit {
+            on_exit(&top);
+        }
+        Some(top)
+    }
+
+    /// Replaces the top state with `next`, firing exit then enter.
+    pub fn transition_to(&mut self, next: S) -> S {
+        let top = self.states.last_mut().expect("state stack is never empty");
+        if let Some(on_exit) = &mut self.on_exit {
+            on_exit(top);
+        }
+        let previous = std::mem::replace(top, next);
+        if let Some(on_enter) = &mut self.on_enter {
+            on_enter(self.states.last().expect("state stack is never empty"));
+        }
+        previous
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{cell::RefCell, rc::Rc};
+
+    type Log = Rc<RefCell<Vec<String>>>;
+
+    fn record_machine(machine: &mut StateMachine<&'static str>, log: &Log) {
+        let enter = log.clone();
+        machine.set_on_enter(move |state| enter.borrow_mut().push(format!("enter {state}")));
+        let exit = log.clone();
+        machine.set_on_exit(move |state| exit.borrow_mut().push(format!("exit {state}")));
+    }
+
+    fn record_stack(stack: &mut StateStack<&'static str>, log: &Log) {
+        let enter = log.clone();
+        stack.set_on_enter(move |state| enter.borrow_mut().push(format!("enter {state}")));
+        let exit = log.clone();
+        stack.set_on_exit(move |state| exit.borrow_mut().push(format!("exit {state}")));
+    }
+
+    #[test]
+    fn transition_fires_exit_then_enter() {
+        let log = Log::default();
+        let mut machine = StateMachine::new("menu");
+        record_machine(&mut machine, &log);
+
+        assert_eq!(machine.transition_to("playing"), "menu");
+        assert_eq!(*machine.current(), "playing");
+        assert_eq!(*log.borrow(), ["exit menu", "enter playing"]);
+    }
+
+    #[test]
+    fn pop_restores_the_previous_state() {
+        let log = Log::default();
+        let mut stack = StateStack::new("playing");
+        record_stack(&mut stack, &log);
+
+        stack.push("paused");
+        assert_eq!(*stack.current(), "paused");
+        assert_eq!(stack.depth(), 2);
+
+        assert_eq!(stack.pop(), Some("paused"));
+        assert_eq!(*stack.current(), "playing");
+        assert_eq!(stack.depth(), 1);
+        assert_eq!(*log.borrow(), ["enter paused", "exit paused"]);
+    }
+
+    #[test]
+    fn pop_keeps_the_bottom_state() {
+        let log = Log::default();
+        let mut stack = StateStack::new("menu");
+        record_stack(&mut stack, &log);
+
+        assert_eq!(stack.pop(), None);
+        assert_eq!(*stack.current(), "menu");
+        assert!(log.borrow().is_empty());
+    }
+
+    #[test]
+    fn stack_transition_replaces_only_the_top() {
+        let log = Log::default();
+        let mut stack = StateStack::new("playing");
+        stack.push("paused");
+        record_stack(&mut stack, &log);
+
+        assert_eq!(stack.transition_to("options"), "paused");
+        assert_eq!(stack.pop(), Some("options"));
+        assert_eq!(*stack.current(), "playing");
+        assert_eq!(*log.borrow(), ["exit paused", "enter options", "exit options"]);
+    }
+}
@@ -0,0 +1,351 @@
+use std::time::{Duration, Instant};
+
+/// Frame timing, advanced once per frame with [`Time::update`].
+pub struct Time {
+    start: Instant,
+    last_update: Instant,
+    delta: f32,
+    total: f32,
+    unscaled_delta: f32,
+    real_total: f32,
+    average_frame_time: f32,
+    time_scale: f32,
+    paused: bool,
+    frame_count: u64,
+    fixed_timestep: f32,
+    max_fixed_steps: u32,
+    fixed_accumulator: f32,
+    fixed_steps: u32,
+}
+
+impl Default for Time {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Time {
+    pub fn new() -> Self {
+        let now = Instant::now();
+        Self {
+            start: now,
+            last_update: now,
+            delta: 0.0,
+            total: 0.0,
+            unscaled_delta: 0.0,
+            real_total: 0.0,
+            average_frame_time: 0.0,
+            time_scale: 1.0,
+            paused: false,
+            frame_count: 0,
+            fixed_timestep: 1.0 / 60.0,
+            max_fixed_steps: 5,
+            fixed_accumulator: 0.0,
+            fixed_steps: 0,
+        }
+    }
+
+    pub fn update(&mut self) {
+        let now = Instant::now();
+        let delta = now.duration_since(self.last_update).as_secs_f32();
+        self.last_update = now;
+        self.update_with_delta(delta);
+    }
+
+    /// Advances by a given delta instead of reading the clock, for replays
+    /// and simulated frames. [`Time::seconds`] still follows the clock.
+    pub fn update_with_delta(&mut self, delta: f32) {
+        self.unscaled_delta = delta;
+        self.real_total += delta;
+        self.average_frame_time = if self.frame_count == 0 {
+            delta
+        } else {
+            self.average_frame_time + (delta - self.average_frame_time) * 0.1
+        };
+        self.delta = if self.paused { 0.0 } else { delta * self.time_scale };
+        self.total += self.delta;
+        self.frame_count += 1;
+
+        self.fixed_accumulator += self.delta;
+        let steps = (self.fixed_accumulator / self.fixed_timestep) as u32;
+        self.fixed_steps = steps.min(self.max_fixed_steps);
+        self.fixed_accumulator -= self.fixed_steps as f32 * self.fixed_timestep;
+        if steps > self.max_fixed_steps {
+            // Too far behind to catch up: drop the backlog rather than
+            // spending ever longer frames on fixed updates.
+            self.fixed_accumulator %= self.fixed_timestep;
+        }
+    }
+
+    /// Sets the fixed update step and how many steps a single frame may run
+    /// before the remaining backlog is dropped.
+    ///
+    /// # Panics
+    ///
+    /// Panics unless `timestep` is positive and finite.
+    pub fn set_fixed_timestep(&mut self, timestep: f32, max_steps: u32) {
+        assert!(
+            timestep > 0.0 && timestep.is_finite(),
+            "fixed timestep must be positive and finite, got {timestep}"
+        );
+        self.fixed_timestep = timestep;
+        self.max_fixed_steps = max_steps;
+    }
+
+    pub fn fixed_timestep(&self) -> f32 {
+        self.fixed_timestep
+    }
+
+    /// How many fixed updates to run this frame, each advancing by
+    /// [`Time::fixed_timestep`].
+    pub fn fixed_steps(&self) -> u32 {
+        self.fixed_steps
+    }
+
+    /// How far, from 0 to 1, the frame is between the last fixed update and
+    /// the next, for interpolating rendered state.
+    pub fn fixed_alpha(&self) -> f32 {
+        self.fixed_accumulator / self.fixed_timestep
+    }
+
+    /// Game-time seconds since the last update: scaled by
+    /// [`Time::time_scale`], and zero while paused.
+    pub fn delta(&self) -> f32 {
+        self.delta
+    }
+
+    /// Real seconds since the last update, ignoring scale and pause. Use it
+    /// for UI and anything else that must keep moving in a pause menu.
+    pub fn unscaled_delta(&self) -> f32 {
+        self.unscaled_delta
+    }
+
+    /// Game time so far, the sum of every [`Time::delta`]. Handy for timers,
+    /// but it drifts over long sessions; use [`Time::seconds`] to drive
+    /// animation.
+    pub fn total(&self) -> f32 {
+        self.total
+    }
+
+    /// Sum of every [`Time::unscaled_delta`].
+    pub fn real_total(&self) -> f32 {
+        self.real_total
+    }
+
+    /// Smoothed [`Time::unscaled_delta`], steady enough to show as a frame
+    /// time or FPS readout.
+    pub fn average_frame_time(&self) -> f32 {
+        self.average_frame_time
+    }
+
+    pub fn time_scale(&self) -> f32 {
+        self.time_scale
+    }
+
+    /// Multiplier applied to game time, e.g. 0.5 for slow motion. Negative
+    /// values are treated as zero.
+    pub fn set_time_scale(&mut self, scale: f32) {
+        self.time_scale = scale.max(0.0);
+    }
+
+    pub fn pause(&mut self) {
+        self.paused = true;
+    }
+
+    pub fn resume(&mut self) {
+        self.paused = false;
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.paused
+    }
+
+    pub fn frame_count(&self) -> u64 {
+        self.frame_count
+    }
+
+    /// Monotonic seconds since this `Time` was created, read straight from
+    /// the clock rather than accumulated, so `sin(time.seconds())` stays
+    /// accurate no matter how long the game runs.
+    pub fn seconds(&self) -> f64 {
+        self.start.elapsed().as_secs_f64()
+    }
+}
+
+/// Holds the frame rate at or below a cap by waiting at the end of each
+/// frame. With vsync on this only has an effect when the cap is below the
+/// display's refresh rate.
+pub struct FrameLimiter {
+    max_fps: Option<u32>,
+    frame_start: Instant,
+}
+
+impl FrameLimiter {
+    /// Sleeping the full remainder can overshoot by a millisecond or more
+    /// on some platforms, so the last stretch is spun instead.
+    const SPIN_MARGIN: Duration = Duration::from_millis(1);
+
+    pub fn new(max_fps: Option<u32>) -> Self {
+        Self {
+            max_fps: max_fps.filter(|&fps| fps > 0),
+            frame_start: Instant::now(),
+        }
+    }
+
+    pub fn max_fps(&self) -> Option<u32> {
+        self.max_fps
+    }
+
+    /// Changes the cap; `None` or `Some(0)` removes it.
+    pub fn set_max_fps(&mut self, max_fps: Option<u32>) {
+        self.max_fps = max_fps.filter(|&fps| fps > 0);
+    }
+
+    /// Waits out the rest of the frame's budget, then starts timing the
+    /// next frame. Call once per frame after presenting. On the web the
+    /// browser paces frames, so this never blocks there.
+    pub fn wait(&mut self) {
+        #[cfg(not(target_arch = "wasm32"))]
+        if let Some(fps) = self.max_fps {
+            let remaining = frame_sleep(fps, self.frame_start.elapsed());
+            let deadline = Instant::now() + remaining;
+            if remaining > Self::SPIN_MARGIN {
+                std::thread::sleep(remaining - Self::SPIN_MARGIN);
+            }
+            while Instant::now() < deadline {
+                std::hint::spin_loop();
+            }
+        }
+        self.frame_start = Instant::now();
+    }
+}
+
+/// How long to wait after a frame that took `frame_time` to hold
+/// `max_fps`. Zero if the frame already used its budget.
+pub fn frame_sleep(max_fps: u32, frame_time: Duration) -> Duration {
+    if max_fps == 0 {
+        return Duration::ZERO;
+    }
+    Duration::from_secs_f64(1.0 / f64::from(max_fps)).saturating_sub(frame_time)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn frame_sleep_fills_the_rest_of_the_budget() {
+        let sleep = frame_sleep(60, Duration::from_millis(5));
+        assert!((sleep.as_secs_f64() - 0.011_667).abs() < 1e-5, "{sleep:?}");
+        assert_eq!(frame_sleep(60, Duration::from_millis(20)), Duration::ZERO);
+        assert_eq!(frame_sleep(0, Duration::ZERO), Duration::ZERO);
+    }
+
+    #[test]
+    fn frame_limiter_ignores_a_zero_cap() {
+        let mut limiter = FrameLimiter::new(Some(0));
+        assert_eq!(limiter.max_fps(), None);
+        limiter.set_max_fps(Some(144));
+        assert_eq!(limiter.max_fps(), Some(144));
+        limiter.set_max_fps(None);
+        assert_eq!(limiter.max_fps(), None);
+    }
+
+    #[test]
+    fn time_scale_scales_game_time_only() {
+        let mut time = Time::new();
+        time.set_time_scale(0.5);
+        time.update_with_delta(0.25);
+        assert_eq!(time.delta(), 0.125);
+        assert_eq!(time.unscaled_delta(), 0.25);
+        assert_eq!(time.total(), 0.125);
+        assert_eq!(time.real_total(), 0.25);
+
+        time.set_time_scale(-1.0);
+        assert_eq!(time.time_scale(), 0.0);
+    }
+
+    #[test]
+    fn pause_zeroes_delta_but_not_unscaled_delta() {
+        let mut time = Time::new();
+        time.pause();
+        assert!(time.is_paused());
+        time.update_with_delta(0.25);
+        assert_eq!(time.delta(), 0.0);
+        assert_eq!(time.unscaled_delta(), 0.25);
+        assert_eq!(time.total(), 0.0);
+        assert_eq!(time.real_total(), 0.25);
+
+        time.resume();
+        time.update_with_delta(0.25);
+        assert_eq!(time.delta(), 0.25);
+        assert_eq!(time.total(), 0.25);
+        assert_eq!(time.frame_count(), 2);
+    }
+
+    #[test]
+    fn fixed_steps_follow_the_accumulator() {
+        let mut time = Time::new();
+        time.set_fixed_timestep(0.25, 5);
+
+        let steps: Vec<_> = [0.125, 0.125, 0.5, 0.375, 0.0]
+            .into_iter()
+            .map(|delta| {
+                time.update_with_delta(delta);
+                time.fixed_steps()
+            })
+            .collect();
+        assert_eq!(steps, vec![0, 1, 2, 1, 0]);
+        assert_eq!(time.fixed_alpha(), 0.5);
+    }
+
+    #[test]
+    fn fixed_steps_are_clamped_and_backlog_dropped() {
+        let mut time = Time::new();
+        time.set_fixed_timestep(0.25, 3);
+        time.update_with_delta(2.125);
+        assert_eq!(time.fixed_steps(), 3);
+        assert_eq!(time.fixed_alpha(), 0.5);
+
+        time.update_with_delta(0.0);
+        assert_eq!(time.fixed_steps(), 0);
+    }
+
+    #[test]
+    fn fixed_steps_follow_game_time() {
+        let mut time = Time::new();
+        time.set_fixed_timestep(0.25, 5);
+        time.pause();
+        time.update_with_delta(1.0);
+        assert_eq!(time.fixed_steps(), 0);
+
+        time.resume();
+        time.set_time_scale(0.5);
+        time.update_with_delta(1.0);
+        assert_eq!(time.fixed_steps(), 2);
+    }
+
+    #[test]
+    #[should_panic(expected = "fixed timestep must be positive")]
+    fn zero_fixed_timestep_is_rejected() {
+        Time::new().set_fixed_timestep(0.0, 5);
+    }
+
+    #[test]
+    fn seconds_follows_the_clock_not_the_deltas() {
+        let reference = Instant::now();
+        let mut time = Time::new();
+
+        let mut last = time.seconds();
+        for _ in 0..3 {
+            time.update_with_delta(100.0);
+            std::thread::sleep(Duration::from_millis(2));
+            let seconds = time.seconds();
+            assert!(seconds > last, "{seconds} <= {last}");
+            assert!((seconds - reference.elapsed().as_secs_f64()).abs() < 0.05);
+            last = seconds;
+        }
+        assert_eq!(time.total(), 300.0);
+        assert!(time.seconds() < 1.0);
+    }
+}
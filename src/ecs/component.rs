@@ -0,0 +1,134 @@
+use std::{
+    any::{Any, type_name},
+    collections::HashMap,
+    hash::{BuildHasherDefault, DefaultHasher},
+};
+
+use super::{entity::Entity, world::World};
+
+/// Hasher for component storages. `DefaultHasher::new()` always uses the
+/// same keys, so unlike `RandomState` the iteration order of a storage is
+/// reproducible run-to-run for the same sequence of operations. It is still
+/// not sorted by entity.
+pub type StorageHasher = BuildHasherDefault<DefaultHasher>;
+
+/// Storage for every instance of one component type.
+pub struct Storage<T> {
+    pub(super) components: HashMap<Entity, T, StorageHasher>,
+}
+
+impl<T> Default for Storage<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> Storage<T> {
+    pub fn new() -> Self {
+        Self {
+            components: HashMap::default(),
+        }
+    }
+
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            components: HashMap::with_capacity_and_hasher(capacity, StorageHasher::default()),
+        }
+    }
+
+    pub fn reserve(&mut self, additional: usize) {
+        self.components.reserve(additional);
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.components.capacity()
+    }
+
+    pub fn insert(&mut self, entity: Entity, component: T) -> Option<T> {
+        self.components.insert(entity, component)
+    }
+
+    pub fn get(&self, entity: Entity) -> Option<&T> {
+        self.components.get(&entity)
+    }
+
+    pub fn get_mut(&mut self, entity: Entity) -> Option<&mut T> {
+        self.components.get_mut(&entity)
+    }
+
+    pub fn remove(&mut self, entity: Entity) -> Option<T> {
+        self.components.remove(&entity)
+    }
+
+    pub fn contains(&self, entity: Entity) -> bool {
+        self.components.contains_key(&entity)
+    }
+
+    pub fn len(&self) -> usize {
+        self.components.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.components.is_empty()
+    }
+}
+
+/// Type-erased view of a [`Storage`] so the world can hold every component
+/// type in one map.
+pub(super) trait ComponentStorage: Any {
+    fn contains_entity(&self, entity: Entity) -> bool;
+    fn remove_entity(&mut self, entity: Entity);
+    fn component_name(&self) -> &'static str;
+    fn as_any(&self) -> &dyn Any;
+    fn as_any_mut(&mut self) -> &mut dyn Any;
+}
+
+impl<T: 'static> ComponentStorage for Storage<T> {
+    fn contains_entity(&self, entity: Entity) -> bool {
+        self.components.contains_key(&entity)
+    }
+
+    fn remove_entity(&mut self, entity: Entity) {
+        self.components.remove(&entity);
+    }
+
+    fn component_name(&self) -> &'static str {
+        type_name::<T>()
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
+
+/// A group of components added to an entity together. Implemented for
+/// tuples of up to eight components.
+pub trait Bundle {
+    fn insert_into(self, world: &mut World, entity: Entity);
+}
+
+macro_rules! impl_bundle {
+    ($($component:ident),+) => {
+        impl<$($component: 'static),+> Bundle for ($($component,)+) {
+            #[allow(non_snake_case)]
+            fn insert_into(self, world: &mut World, entity: Entity) {
+                let ($($component,)+) = self;
+                $(world.add(entity, $component);)+
+            }
+        }
+    };
+}
+
+impl_bundle!(A);
+impl_bundle!(A, B);
+impl_bundle!(A, B, C);
+impl_bundle!(A, B, C, D);
+impl_bundle!(A, B, C, D, E);
+impl_bundle!(A, B, C, D, E, F);
+impl_bundle!(A, B, C, D, E, F, G);
+impl_bundle!(A, B, C, D, E, F, G, H);
+
@@ -0,0 +1,18 @@
+/// Handle to an entity. The generation changes every time an id is
+/// recycled, so handles kept after a despawn stop matching the new entity.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Entity {
+    pub(super) id: u32,
+    pub(super) generation: u32,
+}
+
+impl Entity {
+    /// Slot index, shared by every generation of this entity.
+    pub fn id(&self) -> u32 {
+        self.id
+    }
+
+    pub fn generation(&self) -> u32 {
+        self.generation
+    }
+}
@@ -1,9 +1,13 @@
 //! Entity-Component-System (ECS) module.
 //!
-//! You can add submodules here later, for example:
 //! - `entity` for entity handles/IDs
 //! - `component` for component storage and definitions
-//! - `system` for systems and scheduling
+//! - `query` for iterators over one or two component types
 //! - `world` for the main ECS world/registry
+//!
+//! A `system` module for systems and scheduling can be added here later.
 
-
+pub mod component;
+pub mod entity;
+pub mod query;
+pub mod world;
@@ -0,0 +1,257 @@
+use std::{
+    collections::{HashMap, hash_map},
+    marker::PhantomData,
+    ptr::NonNull,
+};
+
+use super::{
+    component::{Storage, StorageHasher},
+    entity::Entity,
+};
+
+pub struct QueryIter<'a, T> {
+    inner: Option<hash_map::Iter<'a, Entity, T>>,
+}
+
+impl<'a, T> QueryIter<'a, T> {
+    pub(super) fn new(storage: Option<&'a Storage<T>>) -> Self {
+        Self {
+            inner: storage.map(|storage| storage.components.iter()),
+        }
+    }
+}
+
+impl<'a, T> Iterator for QueryIter<'a, T> {
+    type Item = (Entity, &'a T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner
+            .as_mut()?
+            .next()
+            .map(|(&entity, component)| (entity, component))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.as_ref().map_or((0, Some(0)), |inner| inner.size_hint())
+    }
+}
+
+impl<T> ExactSizeIterator for QueryIter<'_, T> {}
+
+pub struct QueryIterMut<'a, T> {
+    inner: Option<hash_map::IterMut<'a, Entity, T>>,
+}
+
+impl<'a, T> QueryIterMut<'a, T> {
+    pub(super) fn new(storage: Option<&'a mut Storage<T>>) -> Self {
+        Self {
+            inner: storage.map(|storage| storage.components.iter_mut()),
+        }
+    }
+}
+
+impl<'a, T> Iterator for QueryIterMut<'a, T> {
+    type Item = (Entity, &'a mut T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner
+            .as_mut()?
+            .next()
+            .map(|(&entity, component)| (entity, component))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.as_ref().map_or((0, Some(0)), |inner| inner.size_hint())
+    }
+}
+
+impl<T> ExactSizeIterator for QueryIterMut<'_, T> {}
+
+enum Join2<'a, A, B> {
+    ByA(hash_map::Iter<'a, Entity, A>, &'a Storage<B>),
+    ByB(hash_map::Iter<'a, Entity, B>, &'a Storage<A>),
+    Empty,
+}
+
+pub struct Query2Iter<'a, A, B> {
+    inner: Join2<'a, A, B>,
+}
+
+impl<'a, A, B> Query2Iter<'a, A, B> {
+    /// Drives the join from whichever storage is smaller.
+    pub(super) fn new(a: Option<&'a Storage<A>>, b: Option<&'a Storage<B>>) -> Self {
+        let inner = match (a, b) {
+            (Some(a), Some(b)) if a.len() <= b.len() => Join2::ByA(a.components.iter(), b),
+            (Some(a), Some(b)) => Join2::ByB(b.components.iter(), a),
+            _ => Join2::Empty,
+        };
+        Self { inner }
+    }
+}
+
+impl<'a, A, B> Iterator for Query2Iter<'a, A, B> {
+    type Item = (Entity, &'a A, &'a B);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match &mut self.inner {
+            Join2::ByA(iter, other) => {
+                let other = *other;
+                iter.find_map(|(&entity, a)| other.get(entity).map(|b| (entity, a, b)))
+            }
+            Join2::ByB(iter, other) => {
+                let other = *other;
+                iter.find_map(|(&entity, b)| other.get(entity).map(|a| (entity, a, b)))
+            }
+            Join2::Empty => None,
+        }
+    }
+}
+
+enum Join2Mut<'a, A, B> {
+    ByA(hash_map::IterMut<'a, Entity, A>, NonNull<HashMap<Entity, B, StorageHasher>>),
+    ByB(hash_map::IterMut<'a, Entity, B>, NonNull<HashMap<Entity, A, StorageHasher>>),
+    Empty,
+}
+
+pub struct Query2IterMut<'a, A, B> {
+    inner: Join2Mut<'a, A, B>,
+    // The looked-up storage is held as a pointer but borrowed for 'a.
+    _borrow: PhantomData<(&'a mut A, &'a mut B)>,
+}
+
+impl<'a, A, B> Query2IterMut<'a, A, B> {
+    /// Drives the join from whichever storage is smaller. `a` and `b` must
+    /// be different storages.
+    pub(super) fn new(a: Option<&'a mut Storage<A>>, b: Option<&'a mut Storage<B>>) -> Self {
+        let inner = match (a, b) {
+            (Some(a), Some(b)) if a.len() <= b.len() => {
+                Join2Mut::ByA(a.components.iter_mut(), NonNull::from(&mut b.components))
+            }
+            (Some(a), Some(b)) => {
+                Join2Mut::ByB(b.components.iter_mut(), NonNull::from(&mut a.components))
+            }
+            _ => Join2Mut::Empty,
+        };
+        Self {
+            inner,
+            _borrow: PhantomData,
+        }
+    }
+}
+
+impl<'a, A, B> Iterator for Query2IterMut<'a, A, B> {
+    type Item = (Entity, &'a mut A, &'a mut B);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        // SAFETY: the pointer comes from a `&'a mut` to a storage distinct
+        // from the one being iterated (query2_mut asserts A != B), and the
+        // driving iterator yields each entity once, so every component
+        // looked up here is borrowed mutably at most once.
+        match &mut self.inner {
+            Join2Mut::ByA(iter, other) => iter.find_map(|(&entity, a)| {
+                let b = unsafe { (*other.as_ptr()).get_mut(&entity)? };
+                Some((entity, a, b))
+            }),
+            Join2Mut::ByB(iter, other) => iter.find_map(|(&entity, b)| {
+                let a = unsafe { (*other.as_ptr()).get_mut(&entity)? };
+                Some((entity, a, b))
+            }),
+            Join2Mut::Empty => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ecs::world::World;
+
+    #[derive(Debug, Clone, PartialEq)]
+    struct Position(i32);
+
+    #[derive(Debug, Clone, PartialEq)]
+    struct Velocity(i32);
+
+    #[test]
+    fn query2_skips_entities_missing_a_component() {
+        let mut world = World::new();
+        let both = world.spawn_bundle((Position(1), Velocity(10)));
+        world.spawn_bundle((Position(2),));
+        world.spawn_bundle((Velocity(30),));
+
+        let found: Vec<_> = world.query2::<Position, Velocity>().collect();
+        assert_eq!(found, vec![(both, &Position(1), &Velocity(10))]);
+    }
+
+    #[test]
+    fn query2_mut_skips_entities_missing_a_component() {
+        let mut world = World::new();
+        let both = world.spawn_bundle((Position(1), Velocity(10)));
+        let only_position = world.spawn_bundle((Position(2),));
+        // More velocities than positions, then the reverse, so both the
+        // ByA and ByB paths run.
+        world.spawn_bundle((Velocity(30),));
+        world.spawn_bundle((Velocity(40),));
+
+        for (_, position, velocity) in world.query2_mut::<Position, Velocity>() {
+            position.0 += velocity.0;
+        }
+        for (_, velocity, position) in world.query2_mut::<Velocity, Position>() {
+            velocity.0 += position.0;
+        }
+
+        assert_eq!(world.get::<Position>(both), Some(&Position(11)));
+        assert_eq!(world.get::<Velocity>(both), Some(&Velocity(21)));
+        assert_eq!(world.get::<Position>(only_position), Some(&Position(2)));
+        assert_eq!(world.query2_mut::<Position, Velocity>().count(), 1);
+    }
+
+    #[test]
+    fn query2_over_empty_storages() {
+        let mut world = World::new();
+        assert_eq!(world.query2::<Position, Velocity>().count(), 0);
+        assert_eq!(world.query2_mut::<Position, Velocity>().count(), 0);
+
+        // Storages that exist but hold nothing.
+        let entity = world.spawn_bundle((Position(1), Velocity(1)));
+        world.despawn(entity);
+        world.spawn_bundle((Position(2),));
+        assert_eq!(world.query2::<Position, Velocity>().count(), 0);
+        assert_eq!(world.query2_mut::<Position, Velocity>().count(), 0);
+        assert_eq!(world.query2_mut::<Velocity, Position>().count(), 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "query2_mut needs two different component types")]
+    fn query2_mut_rejects_the_same_type_twice() {
+        let mut world = World::new();
+        world.spawn_bundle((Position(1),));
+        let _ = world.query2_mut::<Position, Position>();
+    }
+
+    #[test]
+    fn query_len_matches_count() {
+        let mut world = World::new();
+        for i in 0..7 {
+            let entity = world.spawn_bundle((Position(i),));
+            if i % 2 == 0 {
+                world.add(entity, Velocity(i));
+            }
+        }
+        let despawned = world.spawn_bundle((Position(7), Velocity(7)));
+        world.despawn(despawned);
+
+        assert_eq!(world.count::<Position>(), 7);
+        assert_eq!(world.query::<Position>().len(), 7);
+        assert_eq!(world.count::<Velocity>(), 4);
+        assert_eq!(world.query::<Velocity>().len(), 4);
+        assert_eq!(world.query_mut::<Velocity>().len(), 4);
+
+        assert_eq!(world.count::<String>(), 0);
+        assert_eq!(world.query::<String>().len(), 0);
+        assert_eq!(world.query_mut::<String>().len(), 0);
+
+        let mut query = world.query::<Position>();
+        query.next();
+        assert_eq!(query.len(), 6);
+    }
+}
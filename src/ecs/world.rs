@@ -0,0 +1,866 @@
+use std::{
+    any::{Any, TypeId, type_name},
+    collections::{HashMap, hash_map},
+    fmt,
+};
+
+use super::{
+    component::{Bundle, ComponentStorage, Storage},
+    entity::Entity,
+    query::{Query2Iter, Query2IterMut, QueryIter, QueryIterMut},
+};
+use crate::math::Vec2;
+
+/// Copies one component type between entities, registered per type by
+/// [`World::register_clone`].
+#[derive(Clone, Copy)]
+struct ComponentCloner {
+    within: fn(&mut World, Entity, Entity),
+    between: fn(&World, Entity, &mut World, Entity),
+}
+
+pub struct World {
+    entities: Vec<Entity>,
+    dead_entities: Vec<Entity>,
+    // Current generation of each id, indexed by Entity::id.
+    generations: Vec<u32>,
+    // Position of each live entity in `entities`, indexed by Entity::id.
+    // Stale for dead ids.
+    entity_index: Vec<usize>,
+    components: HashMap<TypeId, Box<dyn ComponentStorage>>,
+    // Component types in the order their storages were created, so
+    // per-entity work across storages happens in a repeatable order.
+    component_order: Vec<TypeId>,
+    cloners: HashMap<TypeId, ComponentCloner>,
+    // Global singletons, at most one value per type.
+    resources: HashMap<TypeId, Box<dyn Any>>,
+    strict: bool,
+}
+
+impl Default for World {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl World {
+    pub fn new() -> Self {
+        Self::with_capacity(0)
+    }
+
+    /// Creates a world with room for `entities` live entities (and as many
+    /// recycled ids) before it has to reallocate.
+    pub fn with_capacity(entities: usize) -> Self {
+        Self {
+            entities: Vec::with_capacity(entities),
+            dead_entities: Vec::with_capacity(entities),
+            generations: Vec::with_capacity(entities),
+            entity_index: Vec::with_capacity(entities),
+            components: HashMap::new(),
+            component_order: Vec::new(),
+            cloners: HashMap::new(),
+            resources: HashMap::new(),
+            strict: false,
+        }
+    }
+
+    /// Makes room for at least `additional` more `T` components.
+    pub fn reserve_component<T: 'static>(&mut self, additional: usize) {
+        self.storage_or_insert::<T>().reserve(additional);
+    }
+
+    pub fn spawn(&mut self) -> Entity {
+        let entity = match self.dead_entities.pop() {
+            Some(entity) => entity,
+            None => {
+                let entity = Entity {
+                    id: self.generations.len() as u32,
+                    generation: 0,
+                };
+                self.generations.push(0);
+                self.entity_index.push(0);
+                entity
+            }
+        };
+        self.entity_index[entity.id as usize] = self.entities.len();
+        self.entities.push(entity);
+        entity
+    }
+
+    /// Spawns an entity with every component in `bundle`, e.g.
+    /// `world.spawn_bundle((position, velocity, name))`.
+    pub fn spawn_bundle<B: Bundle>(&mut self, bundle: B) -> Entity {
+        let entity = self.spawn();
+        bundle.insert_into(self, entity);
+        entity
+    }
+
+    /// Spawns a `cols` x `rows` grid of entities, using the bundle `f` builds
+    /// from each cell's column, row and position `origin + (col, row) *
+    /// spacing`. Returns the entities row by row.
+    pub fn spawn_grid<B: Bundle>(
+        &mut self,
+        cols: usize,
+        rows: usize,
+        spacing: Vec2,
+        origin: Vec2,
+        mut f: impl FnMut(usize, usize, Vec2) -> B,
+    ) -> Vec<Entity> {
+        let mut entities = Vec::with_capacity(cols * rows);
+        for row in 0..rows {
+            for col in 0..cols {
+                let position =
+                    origin + Vec2::new(col as f32 * spacing.x, row as f32 * spacing.y);
+                entities.push(self.spawn_bundle(f(col, row, position)));
+            }
+        }
+        entities
+    }
+
+    /// Removes `entity` and drops its components, in the order their types
+    /// were first added to this world.
+    pub fn despawn(&mut self, entity: Entity) {
+        if self.is_alive(entity) {
+            let index = self.entity_index[entity.id as usize];
+            self.entities.swap_remove(index);
+            if let Some(moved) = self.entities.get(index) {
+                self.entity_index[moved.id as usize] = index;
+            }
+            for type_id in &self.component_order {
+                if let Some(storage) = self.components.get_mut(type_id) {
+                    storage.remove_entity(entity);
+                }
+            }
+
+            let generation = &mut self.generations[entity.id as usize];
+            *generation = generation.wrapping_add(1);
+            self.dead_entities.push(Entity {
+                id: entity.id,
+                generation: *generation,
+            });
+        }
+    }
+
+    /// False for despawned entities, including handles whose id has since
+    /// been reused by a newer entity.
+    pub fn is_alive(&self, entity: Entity) -> bool {
+        // Recycled handles sit in dead_entities with the bumped generation
+        // but are never handed out until spawned, so a match means alive.
+        self.generations.get(entity.id as usize) == Some(&entity.generation)
+    }
+
+    pub fn entities(&self) -> &[Entity] {
+        &self.entities
+    }
+
+    /// Attaches `component` to `entity`. Ignored if the entity is dead.
+    pub fn add<T: 'static>(&mut self, entity: Entity, component: T) {
+        if !self.check_alive(entity, format_args!("add::<{}>", type_name::<T>())) {
+            return;
+        }
+        self.storage_or_insert::<T>().insert(entity, component);
+    }
+
+    pub fn remove<T: 'static>(&mut self, entity: Entity) -> Option<T> {
+        if !self.check_alive(entity, format_args!("remove::<{}>", type_name::<T>())) {
+            return None;
+        }
+        self.storage_mut::<T>()?.remove(entity)
+    }
+
+    pub fn get<T: 'static>(&self, entity: Entity) -> Option<&T> {
+        if !self.check_alive(entity, format_args!("get::<{}>", type_name::<T>())) {
+            return None;
+        }
+        self.storage::<T>()?.get(entity)
+    }
+
+    pub fn get_mut<T: 'static>(&mut self, entity: Entity) -> Option<&mut T> {
+        if !self.check_alive(entity, format_args!("get_mut::<{}>", type_name::<T>())) {
+            return None;
+        }
+        self.storage_mut::<T>()?.get_mut(entity)
+    }
+
+    /// In strict mode, `add`, `remove`, `get`, `get_mut`, `clone_entity` and
+    /// `copy_entity_to` log a warning when given a dead entity instead of
+    /// quietly doing nothing. Meant for development builds, to catch stale
+    /// handles early.
+    pub fn set_strict(&mut self, strict: bool) {
+        self.strict = strict;
+    }
+
+    pub fn is_strict(&self) -> bool {
+        self.strict
+    }
+
+    fn check_alive(&self, entity: Entity, operation: fmt::Arguments<'_>) -> bool {
+        let alive = self.is_alive(entity);
+        if !alive && self.strict {
+            log::warn!("World::{} called on dead entity {:?}", operation, entity);
+        }
+        alive
+    }
+
+    /// Removes every `T` from every entity, leaving other components and
+    /// the entities themselves alone.
+    pub fn clear_component<T: 'static>(&mut self) {
+        if let Some(storage) = self.storage_mut::<T>() {
+            storage.components.clear();
+        }
+    }
+
+    /// Alias for [`World::clear_component`].
+    pub fn remove_all<T: 'static>(&mut self) {
+        self.clear_component::<T>();
+    }
+
+    pub fn has<T: 'static>(&self, entity: Entity) -> bool {
+        self.storage::<T>().is_some_and(|storage| storage.contains(entity))
+    }
+
+    /// Type names of every component `entity` has, in the order the types
+    /// were first added to this world. Meant for inspectors and debug
+    /// output; the exact names are whatever [`std::any::type_name`] gives.
+    pub fn component_names(&self, entity: Entity) -> Vec<&'static str> {
+        if !self.is_alive(entity) {
+            return Vec::new();
+        }
+        self.component_order
+            .iter()
+            .map(|type_id| &self.components[type_id])
+            .filter(|storage| storage.contains_entity(entity))
+            .map(|storage| storage.component_name())
+            .collect()
+    }
+
+    /// Number of entities that have a `T`, without iterating them.
+    pub fn count<T: 'static>(&self) -> usize {
+        self.storage::<T>().map_or(0, Storage::len)
+    }
+
+    /// Iterates every entity that has a `T`. The order is unspecified and
+    /// can change between runs; use [`World::query_sorted`] when it matters.
+    pub fn query<T: 'static>(&self) -> QueryIter<'_, T> {
+        QueryIter::new(self.storage::<T>())
+    }
+
+    /// Like [`World::query`], but in ascending entity id order. Sorts
+    /// references to the components, so it costs an allocation per call
+    /// but never clones a `T`.
+    pub fn query_sorted<T: 'static>(&self) -> impl Iterator<Item = (Entity, &T)> {
+        let mut sorted: Vec<_> = self.query::<T>().collect();
+        sorted.sort_unstable_by_key(|&(entity, _)| entity);
+        sorted.into_iter()
+    }
+
+    /// Mutable version of [`World::query`].
+    pub fn query_mut<T: 'static>(&mut self) -> QueryIterMut<'_, T> {
+        QueryIterMut::new(self.storage_mut::<T>())
+    }
+
+    /// Calls `f` for every entity that has a `T`, driving the storage
+    /// directly instead of building a [`QueryIter`].
+    pub fn for_each<T: 'static>(&self, mut f: impl FnMut(Entity, &T)) {
+        if let Some(storage) = self.storage::<T>() {
+            for (&entity, component) in &storage.components {
+                f(entity, component);
+            }
+        }
+    }
+
+    /// Mutable version of [`World::for_each`].
+    pub fn for_each_mut<T: 'static>(&mut self, mut f: impl FnMut(Entity, &mut T)) {
+        if let Some(storage) = self.storage_mut::<T>() {
+            for (&entity, component) in &mut storage.components {
+                f(entity, component);
+            }
+        }
+    }
+
+    /// Iterates every entity that has both an `A` and a `B`, driven by
+    /// whichever storage is smaller. The order is unspecified.
+    pub fn query2<A: 'static, B: 'static>(&self) -> Query2Iter<'_, A, B> {
+        Query2Iter::new(self.storage::<A>(), self.storage::<B>())
+    }
+
+    /// Mutable version of [`World::query2`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `A` and `B` are the same type, since that would hand out
+    /// two mutable references to the same component.
+    pub fn query2_mut<A: 'static, B: 'static>(&mut self) -> Query2IterMut<'_, A, B> {
+        assert_ne!(
+            TypeId::of::<A>(),
+            TypeId::of::<B>(),
+            "query2_mut needs two different component types"
+        );
+
+        let [a, b] = self
+            .components
+            .get_disjoint_mut([&TypeId::of::<A>(), &TypeId::of::<B>()]);
+        let a = a.and_then(|storage| storage.as_any_mut().downcast_mut::<Storage<A>>());
+        let b = b.and_then(|storage| storage.as_any_mut().downcast_mut::<Storage<B>>());
+        Query2IterMut::new(a, b)
+    }
+
+    /// Yields the `T` of each entity in `entities` that has one, in the
+    /// order given. Cheaper than filtering a full [`World::query`] when the
+    /// subset is small.
+    pub fn query_subset<'a, T: 'static>(
+        &'a self,
+        entities: &'a [Entity],
+    ) -> impl Iterator<Item = (Entity, &'a T)> + 'a {
+        let storage = self.storage::<T>();
+        entities.iter().filter_map(move |&entity| {
+            storage?.get(entity).map(|component| (entity, component))
+        })
+    }
+
+    /// Allows [`World::clone_entity`] and [`World::copy_entity_to`] to copy
+    /// `T`. Component types that aren't registered are skipped when cloning.
+    pub fn register_clone<T: Clone + 'static>(&mut self) {
+        self.cloners.insert(
+            TypeId::of::<T>(),
+            ComponentCloner {
+                within: |world, from, to| {
+                    if let Some(component) = world.get::<T>(from).cloned() {
+                        world.add(to, component);
+                    }
+                },
+                between: |source, from, target, to| {
+                    if let Some(component) = source.get::<T>(from) {
+                        target.add(to, component.clone());
+                    }
+                },
+            },
+        );
+    }
+
+    /// Spawns a new entity carrying copies of `entity`'s cloneable components.
+    /// Returns `None`, spawning nothing, if `entity` is dead.
+    pub fn clone_entity(&mut self, entity: Entity) -> Option<Entity> {
+        if !self.check_alive(entity, format_args!("clone_entity")) {
+            return None;
+        }
+        let clone = self.spawn();
+        for cloner in self.cloners_for(entity) {
+            (cloner.within)(self, entity, clone);
+        }
+        Some(clone)
+    }
+
+    /// Spawns an entity in `target` carrying copies of `entity`'s cloneable
+    /// components. `target` learns the clone registrations it needs, so the
+    /// copy can itself be cloned there. Returns `None`, spawning nothing, if
+    /// `entity` is dead.
+    pub fn copy_entity_to(&self, entity: Entity, target: &mut World) -> Option<Entity> {
+        if !self.check_alive(entity, format_args!("copy_entity_to")) {
+            return None;
+        }
+        let copy = target.spawn();
+        for type_id in &self.component_order {
+            let Some(cloner) = self.cloners.get(type_id) else {
+                continue;
+            };
+            if self.components[type_id].contains_entity(entity) {
+                (cloner.between)(self, entity, target, copy);
+                target.cloners.insert(*type_id, *cloner);
+            }
+        }
+        self.warn_uncloneable(entity);
+        Some(copy)
+    }
+
+    fn cloners_for(&self, entity: Entity) -> Vec<ComponentCloner> {
+        self.warn_uncloneable(entity);
+        self.component_order
+            .iter()
+            .filter(|type_id| self.components[*type_id].contains_entity(entity))
+            .filter_map(|type_id| self.cloners.get(type_id).copied())
+            .collect()
+    }
+
+    fn warn_uncloneable(&self, entity: Entity) {
+        let skipped = self
+            .components
+            .iter()
+            .filter(|(type_id, storage)| {
+                storage.contains_entity(entity) && !self.cloners.contains_key(type_id)
+            })
+            .count();
+        if skipped > 0 {
+            log::warn!(
+                "Skipped {} component(s) of {:?} not registered with World::register_clone",
+                skipped,
+                entity
+            );
+        }
+    }
+
+    /// Stores `resource` as the world's single `T`, replacing any previous
+    /// one. Resources hold global state such as the score or an RNG that
+    /// doesn't belong to any entity.
+    pub fn insert_resource<T: 'static>(&mut self, resource: T) {
+        self.resources.insert(TypeId::of::<T>(), Box::new(resource));
+    }
+
+    pub fn resource<T: 'static>(&self) -> Option<&T> {
+        self.resources.get(&TypeId::of::<T>())?.downcast_ref()
+    }
+
+    pub fn resource_mut<T: 'static>(&mut self) -> Option<&mut T> {
+        self.resources.get_mut(&TypeId::of::<T>())?.downcast_mut()
+    }
+
+    pub fn remove_resource<T: 'static>(&mut self) -> Option<T> {
+        let resource = self.resources.remove(&TypeId::of::<T>())?;
+        Some(*resource.downcast().expect("resource stored under the wrong TypeId"))
+    }
+
+    pub fn storage<T: 'static>(&self) -> Option<&Storage<T>> {
+        self.components
+            .get(&TypeId::of::<T>())
+            .and_then(|storage| storage.as_any().downcast_ref::<Storage<T>>())
+    }
+
+    pub fn storage_mut<T: 'static>(&mut self) -> Option<&mut Storage<T>> {
+        self.components
+            .get_mut(&TypeId::of::<T>())
+            .and_then(|storage| storage.as_any_mut().downcast_mut::<Storage<T>>())
+    }
+
+    fn storage_or_insert<T: 'static>(&mut self) -> &mut Storage<T> {
+        let storage = match self.components.entry(TypeId::of::<T>()) {
+            hash_map::Entry::Occupied(entry) => entry.into_mut(),
+            hash_map::Entry::Vacant(entry) => {
+                self.component_order.push(TypeId::of::<T>());
+                entry.insert(Box::new(Storage::<T>::new()))
+            }
+        };
+        storage
+            .as_any_mut()
+            .downcast_mut::<Storage<T>>()
+            .expect("component storage registered under the wrong TypeId")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, Clone, PartialEq)]
+    struct Position(i32);
+
+    #[derive(Debug, Clone, PartialEq)]
+    struct Velocity(i32);
+
+    #[test]
+    fn respawned_id_does_not_revive_old_handle() {
+        let mut world = World::new();
+        let old = world.spawn();
+        world.add(old, Position(1));
+        world.despawn(old);
+        assert!(!world.is_alive(old));
+
+        let new = world.spawn();
+        assert_eq!(new.id(), old.id());
+        assert_ne!(new.generation(), old.generation());
+        assert!(world.is_alive(new));
+        assert!(!world.is_alive(old));
+
+        // The stale handle can neither read nor write the new entity.
+        world.add(old, Position(2));
+        assert_eq!(world.get::<Position>(old), None);
+        assert_eq!(world.get_mut::<Position>(old), None);
+        assert_eq!(world.get::<Position>(new), None);
+        world.add(new, Position(3));
+        assert_eq!(world.get::<Position>(old), None);
+        assert_eq!(world.get::<Position>(new), Some(&Position(3)));
+    }
+
+    #[test]
+    fn despawning_a_stale_handle_leaves_the_new_entity_alone() {
+        let mut world = World::new();
+        let old = world.spawn();
+        world.despawn(old);
+        let new = world.spawn_bundle((Position(1),));
+
+        world.despawn(old);
+        assert!(world.is_alive(new));
+        assert_eq!(world.get::<Position>(new), Some(&Position(1)));
+        assert_eq!(world.entities(), &[new]);
+    }
+
+    #[test]
+    fn despawning_half_of_many_entities_keeps_bookkeeping_consistent() {
+        let mut world = World::new();
+        let entities: Vec<_> = (0..10_000).map(|_| world.spawn()).collect();
+        for &entity in entities.iter().step_by(2) {
+            world.despawn(entity);
+        }
+
+        assert_eq!(world.entities().len(), 5_000);
+        for (i, &entity) in entities.iter().enumerate() {
+            assert_eq!(world.is_alive(entity), i % 2 == 1);
+        }
+        // Every survivor is listed exactly once.
+        let mut listed = world.entities().to_vec();
+        listed.sort();
+        let survivors: Vec<_> = entities.iter().copied().skip(1).step_by(2).collect();
+        assert_eq!(listed, survivors);
+
+        // The index still points at the right slots after all the swaps.
+        for &entity in &survivors {
+            world.despawn(entity);
+        }
+        assert!(world.entities().is_empty());
+    }
+
+    #[test]
+    fn with_capacity_preallocates() {
+        let mut world = World::with_capacity(64);
+        assert!(world.entities.capacity() >= 64);
+        assert!(world.dead_entities.capacity() >= 64);
+        assert!(world.generations.capacity() >= 64);
+
+        world.reserve_component::<Position>(32);
+        let storage = world.storage::<Position>().unwrap();
+        assert!(storage.capacity() >= 32);
+        assert!(storage.is_empty());
+    }
+
+    #[test]
+    fn with_capacity_behaves_like_new() {
+        for mut world in [World::new(), World::with_capacity(16)] {
+            assert!(world.entities().is_empty());
+
+            let a = world.spawn();
+            let b = world.spawn();
+            world.add(a, Position(1));
+            world.add(a, Velocity(2));
+            world.add(b, Position(3));
+
+            assert_eq!(world.entities(), &[a, b]);
+            assert_eq!(world.get::<Position>(a), Some(&Position(1)));
+            assert_eq!(world.get::<Velocity>(b), None);
+            assert!(world.has::<Velocity>(a));
+
+            world.get_mut::<Position>(b).unwrap().0 = 4;
+            let mut positions: Vec<_> = world.query::<Position>().map(|(_, p)| p.0).collect();
+            positions.sort();
+            assert_eq!(positions, vec![1, 4]);
+
+            assert_eq!(world.remove::<Velocity>(a), Some(Velocity(2)));
+            assert!(!world.has::<Velocity>(a));
+        }
+    }
+
+    #[test]
+    fn copy_entity_to_keeps_registration_order() {
+        #[derive(Clone)]
+        struct Name;
+
+        let mut source = World::new();
+        source.register_clone::<Velocity>();
+        source.register_clone::<Name>();
+        source.register_clone::<Position>();
+        let entity = source.spawn_bundle((Velocity(1), Position(2), Name));
+
+        let mut target = World::new();
+        source.copy_entity_to(entity, &mut target).unwrap();
+        assert_eq!(target.component_order, source.component_order);
+
+        let clone = source.clone_entity(entity).unwrap();
+        assert_eq!(source.component_names(clone), source.component_names(entity));
+    }
+
+    #[test]
+    fn cloning_a_dead_entity_spawns_nothing() {
+        let mut world = World::new();
+        world.register_clone::<Position>();
+        let entity = world.spawn_bundle((Position(1),));
+        world.despawn(entity);
+
+        let mut target = World::new();
+        assert_eq!(world.clone_entity(entity), None);
+        assert_eq!(world.copy_entity_to(entity, &mut target), None);
+        assert!(world.entities().is_empty());
+        assert!(target.entities().is_empty());
+    }
+
+    #[test]
+    fn dead_entities_are_ignored_in_strict_and_normal_mode() {
+        for strict in [false, true] {
+            let mut world = World::new();
+            world.set_strict(strict);
+            assert_eq!(world.is_strict(), strict);
+
+            let alive = world.spawn_bundle((Position(1),));
+            let dead = world.spawn_bundle((Position(2),));
+            world.despawn(dead);
+
+            world.add(dead, Velocity(3));
+            assert_eq!(world.get::<Velocity>(dead), None);
+            assert_eq!(world.get_mut::<Position>(dead), None);
+            assert_eq!(world.remove::<Position>(dead), None);
+            assert_eq!(world.count::<Velocity>(), 0);
+            assert_eq!(world.get::<Position>(alive), Some(&Position(1)));
+        }
+    }
+
+    #[test]
+    fn despawn_drops_components_in_registration_order() {
+        use std::{cell::RefCell, rc::Rc};
+
+        type Log = Rc<RefCell<Vec<&'static str>>>;
+
+        struct First(Log);
+        struct Second(Log);
+
+        impl Drop for First {
+            fn drop(&mut self) {
+                self.0.borrow_mut().push("first");
+            }
+        }
+
+        impl Drop for Second {
+            fn drop(&mut self) {
+                self.0.borrow_mut().push("second");
+            }
+        }
+
+        let log = Log::default();
+        let mut world = World::new();
+        let registers = world.spawn();
+        world.add(registers, First(log.clone()));
+        world.add(registers, Second(log.clone()));
+
+        // Added in the opposite order to how the types were registered.
+        let reversed = world.spawn();
+        world.add(reversed, Second(log.clone()));
+        world.add(reversed, First(log.clone()));
+
+        world.despawn(reversed);
+        assert_eq!(*log.borrow(), ["first", "second"]);
+        world.despawn(registers);
+        assert_eq!(*log.borrow(), ["first", "second", "first", "second"]);
+    }
+
+    #[test]
+    fn clones_copy_values_and_are_independent() {
+        let mut world = World::new();
+        world.register_clone::<Position>();
+        world.register_clone::<Velocity>();
+        let entity = world.spawn_bundle((Position(1), Velocity(2)));
+
+        let clone = world.clone_entity(entity).unwrap();
+        assert_eq!(world.get::<Position>(clone), Some(&Position(1)));
+        assert_eq!(world.get::<Velocity>(clone), Some(&Velocity(2)));
+        world.get_mut::<Position>(clone).unwrap().0 = 10;
+        assert_eq!(world.get::<Position>(entity), Some(&Position(1)));
+
+        let mut target = World::new();
+        let copy = world.copy_entity_to(entity, &mut target).unwrap();
+        assert_eq!(target.get::<Position>(copy), Some(&Position(1)));
+        assert_eq!(target.get::<Velocity>(copy), Some(&Velocity(2)));
+        target.get_mut::<Velocity>(copy).unwrap().0 = 20;
+        assert_eq!(world.get::<Velocity>(entity), Some(&Velocity(2)));
+        assert_eq!(world.get::<Velocity>(clone), Some(&Velocity(2)));
+    }
+
+    #[test]
+    fn same_operations_give_the_same_query_order() {
+        fn build() -> World {
+            let mut world = World::new();
+            let entities: Vec<_> = (0..100).map(|_| world.spawn()).collect();
+            for (i, &entity) in entities.iter().enumerate() {
+                world.add(entity, Position(i as i32));
+            }
+            for &entity in entities.iter().step_by(3) {
+                world.despawn(entity);
+            }
+            for i in 0..20 {
+                world.spawn_bundle((Position(100 + i),));
+            }
+            world
+        }
+
+        let a = build();
+        let b = build();
+        let order_a: Vec<_> = a.query::<Position>().map(|(entity, _)| entity).collect();
+        let order_b: Vec<_> = b.query::<Position>().map(|(entity, _)| entity).collect();
+        assert_eq!(order_a.len(), 86);
+        assert_eq!(order_a, order_b);
+    }
+
+    #[test]
+    fn resources_are_stored_per_type() {
+        #[derive(Debug, PartialEq)]
+        struct Score(u32);
+        #[derive(Debug, PartialEq)]
+        struct Seed(u64);
+
+        let mut world = World::new();
+        world.insert_resource(Score(0));
+        world.insert_resource(Seed(42));
+
+        world.resource_mut::<Score>().unwrap().0 += 5;
+        assert_eq!(world.resource::<Score>(), Some(&Score(5)));
+        assert_eq!(world.resource::<Seed>(), Some(&Seed(42)));
+
+        assert_eq!(world.remove_resource::<Score>(), Some(Score(5)));
+        assert_eq!(world.resource::<Score>(), None);
+        assert_eq!(world.remove_resource::<Score>(), None);
+        assert_eq!(world.resource::<Seed>(), Some(&Seed(42)));
+    }
+
+    #[test]
+    fn query_subset_yields_the_intersection_in_the_given_order() {
+        let mut world = World::new();
+        let a = world.spawn_bundle((Position(1),));
+        let b = world.spawn_bundle((Velocity(2),));
+        let c = world.spawn_bundle((Position(3),));
+        let d = world.spawn_bundle((Position(4),));
+        let dead = world.spawn_bundle((Position(5),));
+        world.despawn(dead);
+
+        let subset = [d, b, a, dead];
+        let found: Vec<_> = world.query_subset::<Position>(&subset).collect();
+        assert_eq!(found, vec![(d, &Position(4)), (a, &Position(1))]);
+        assert!(!found.iter().any(|&(entity, _)| entity == c));
+        assert_eq!(world.query_subset::<String>(&subset).count(), 0);
+    }
+
+    #[test]
+    fn clear_component_leaves_other_components_and_entities() {
+        use crate::{math::Transform2D, scene::movement::Velocity2D};
+
+        let mut world = World::new();
+        let entities: Vec<_> = (0..5)
+            .map(|i| {
+                let transform = Transform2D::new(Vec2::new(i as f32, 0.0), 0.0, Vec2::ONE);
+                world.spawn_bundle((transform, Velocity2D::new(Vec2::ONE, 1.0)))
+            })
+            .collect();
+
+        world.clear_component::<Velocity2D>();
+        assert_eq!(world.count::<Velocity2D>(), 0);
+        for (i, &entity) in entities.iter().enumerate() {
+            assert!(world.is_alive(entity));
+            assert!(!world.has::<Velocity2D>(entity));
+            let transform = Transform2D::new(Vec2::new(i as f32, 0.0), 0.0, Vec2::ONE);
+            assert_eq!(world.get::<Transform2D>(entity), Some(&transform));
+        }
+
+        world.add(entities[0], Velocity2D::default());
+        world.remove_all::<Velocity2D>();
+        assert_eq!(world.count::<Velocity2D>(), 0);
+        assert_eq!(world.count::<Transform2D>(), 5);
+    }
+
+    #[test]
+    fn for_each_mut_updates_every_component() {
+        let mut world = World::new();
+        let entities: Vec<_> = (0..10).map(|i| world.spawn_bundle((Position(i),))).collect();
+        world.spawn_bundle((Velocity(0),));
+
+        world.for_each_mut::<Position>(|_, position| position.0 *= 2);
+
+        for (i, &entity) in entities.iter().enumerate() {
+            assert_eq!(world.get::<Position>(entity), Some(&Position(i as i32 * 2)));
+        }
+    }
+
+    #[test]
+    fn for_each_visits_the_same_entities_as_query() {
+        use std::collections::HashSet;
+
+        let mut world = World::new();
+        for i in 0..10 {
+            let entity = world.spawn_bundle((Position(i),));
+            if i % 3 == 0 {
+                world.despawn(entity);
+            }
+        }
+        world.spawn_bundle((Velocity(0),));
+
+        let mut visited = HashSet::new();
+        world.for_each::<Position>(|entity, _| {
+            assert!(visited.insert(entity), "{entity:?} visited twice");
+        });
+        let queried: HashSet<_> = world.query::<Position>().map(|(entity, _)| entity).collect();
+        assert_eq!(visited.len(), 6);
+        assert_eq!(visited, queried);
+    }
+
+    #[test]
+    fn spawn_bundle_adds_every_component() {
+        #[derive(Debug, PartialEq)]
+        struct Name(&'static str);
+
+        let mut world = World::new();
+        let entity = world.spawn_bundle((Position(1), Velocity(2), Name("player")));
+
+        assert_eq!(world.get::<Position>(entity), Some(&Position(1)));
+        assert_eq!(world.get::<Velocity>(entity), Some(&Velocity(2)));
+        assert_eq!(world.get::<Name>(entity), Some(&Name("player")));
+        assert_eq!(world.component_names(entity).len(), 3);
+    }
+
+    #[test]
+    fn spawn_grid_lays_out_rows_of_columns() {
+        let mut world = World::new();
+        let spacing = Vec2::new(2.0, 3.0);
+        let origin = Vec2::new(10.0, -5.0);
+        let entities =
+            world.spawn_grid(3, 2, spacing, origin, |col, row, position| ((col, row), position));
+
+        assert_eq!(entities.len(), 6);
+        for (i, &entity) in entities.iter().enumerate() {
+            let (col, row) = (i % 3, i / 3);
+            assert_eq!(world.get::<(usize, usize)>(entity), Some(&(col, row)));
+            let expected = origin + Vec2::new(col as f32 * spacing.x, row as f32 * spacing.y);
+            assert_eq!(world.get::<Vec2>(entity), Some(&expected));
+        }
+    }
+
+    #[test]
+    fn query_sorted_yields_ascending_ids() {
+        let mut world = World::new();
+        let entities: Vec<_> = (0..6).map(|_| world.spawn()).collect();
+        world.despawn(entities[1]);
+        world.despawn(entities[3]);
+        // Recycled ids come back newest first, so ids are now out of
+        // spawn order.
+        let respawned = [world.spawn(), world.spawn()];
+        assert_eq!(respawned.map(|entity| entity.id()), [3, 1]);
+
+        let mut alive = world.entities().to_vec();
+        alive.reverse();
+        alive.swap(0, 2);
+        for (i, &entity) in alive.iter().enumerate() {
+            world.add(entity, Position(i as i32));
+        }
+
+        let ids: Vec<_> = world.query_sorted::<Position>().map(|(entity, _)| entity.id()).collect();
+        assert_eq!(ids, vec![0, 1, 2, 3, 4, 5]);
+        assert_eq!(world.query_sorted::<Velocity>().count(), 0);
+    }
+
+    #[test]
+    fn component_names_lists_the_entity_types() {
+        let mut world = World::new();
+        world.spawn_bundle((String::from("other"),));
+        let entity = world.spawn_bundle((Velocity(1), Position(2)));
+
+        assert_eq!(
+            world.component_names(entity),
+            vec![type_name::<Velocity>(), type_name::<Position>()]
+        );
+
+        world.despawn(entity);
+        assert!(world.component_names(entity).is_empty());
+    }
+}
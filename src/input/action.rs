@@ -0,0 +1,140 @@
+use super::keyboard::Keyboard;
+use std::collections::HashMap;
+use winit::keyboard::KeyCode;
+
+/// Named input actions bound to one or more keys, so game code can ask
+/// about "jump" instead of hardcoding `KeyCode::Space`.
+///
+/// Bindings can be changed at any time, e.g. from a rebind menu. Queries
+/// read the given [`Keyboard`]'s current frame, and an action or axis with
+/// no bindings is never active.
+#[derive(Debug, Clone, Default)]
+pub struct ActionMap {
+    actions: HashMap<String, Vec<KeyCode>>,
+    axes: HashMap<String, (KeyCode, KeyCode)>,
+}
+
+impl ActionMap {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds `key` to the keys that trigger `action`.
+    pub fn bind(&mut self, action: &str, key: KeyCode) {
+        let keys = self.actions.entry(action.to_owned()).or_default();
+        if !keys.contains(&key) {
+            keys.push(key);
+        }
+    }
+
+    pub fn unbind(&mut self, action: &str, key: KeyCode) {
+        if let Some(keys) = self.actions.get_mut(action) {
+            keys.retain(|&bound| bound != key);
+        }
+    }
+
+    /// Removes every key bound to `action`.
+    pub fn clear_bindings(&mut self, action: &str) {
+        self.actions.remove(action);
+    }
+
+    pub fn bindings(&self, action: &str) -> &[KeyCode] {
+        self.actions.get(action).map_or(&[], Vec::as_slice)
+    }
+
+    /// Binds `axis` to a key pair, replacing any previous pair.
+    pub fn bind_axis(&mut self, axis: &str, negative: KeyCode, positive: KeyCode) {
+        self.axes.insert(axis.to_owned(), (negative, positive));
+    }
+
+    /// Whether any key bound to `action` is held.
+    pub fn action_down(&self, keyboard: &Keyboard, action: &str) -> bool {
+        self.bindings(action).iter().any(|&key| keyboard.is_pressed(key))
+    }
+
+    /// Whether any key bound to `action` went down this frame.
+    pub fn action_pressed(&self, keyboard: &Keyboard, action: &str) -> bool {
+        self.bindings(action).iter().any(|&key| keyboard.was_just_pressed(key))
+    }
+
+    /// Whether any key bound to `action` went up this frame.
+    pub fn action_released(&self, keyboard: &Keyboard, action: &str) -> bool {
+        self.bindings(action).iter().any(|&key| keyboard.was_just_released(key))
+    }
+
+    /// -1.0 while only the negative key is held, 1.0 while only the
+    /// positive one is, and 0.0 when neither or both are.
+    pub fn action_axis(&self, keyboard: &Keyboard, axis: &str) -> f32 {
+        let Some(&(negative, positive)) = self.axes.get(axis) else {
+            return 0.0;
+        };
+        let mut value = 0.0;
+        if keyboard.is_pressed(negative) {
+            value -= 1.0;
+        }
+        if keyboard.is_pressed(positive) {
+            value += 1.0;
+        }
+        value
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use winit::keyboard::{Key, NativeKey, PhysicalKey};
+
+    fn keyboard_holding(keys: &[KeyCode]) -> Keyboard {
+        let mut keyboard = Keyboard::new();
+        let logical = Key::Unidentified(NativeKey::Unidentified);
+        for &key in keys {
+            keyboard.handle_key_event(PhysicalKey::Code(key), &logical, true, false);
+        }
+        keyboard.begin_frame(0.0);
+        keyboard
+    }
+
+    #[test]
+    fn action_is_down_if_any_binding_is_held() {
+        let mut actions = ActionMap::new();
+        actions.bind("jump", KeyCode::Space);
+        actions.bind("jump", KeyCode::KeyW);
+
+        for held in [[KeyCode::Space], [KeyCode::KeyW]] {
+            let keyboard = keyboard_holding(&held);
+            assert!(actions.action_down(&keyboard, "jump"));
+            assert!(actions.action_pressed(&keyboard, "jump"));
+            assert!(!actions.action_released(&keyboard, "jump"));
+        }
+        assert!(!actions.action_down(&keyboard_holding(&[KeyCode::KeyA]), "jump"));
+        assert!(!actions.action_down(&keyboard_holding(&[KeyCode::Space]), "unbound"));
+    }
+
+    #[test]
+    fn rebinding_takes_effect_immediately() {
+        let mut actions = ActionMap::new();
+        actions.bind("jump", KeyCode::Space);
+        actions.bind("jump", KeyCode::Space);
+        assert_eq!(actions.bindings("jump"), &[KeyCode::Space]);
+
+        let keyboard = keyboard_holding(&[KeyCode::Space]);
+        actions.unbind("jump", KeyCode::Space);
+        assert!(!actions.action_down(&keyboard, "jump"));
+        actions.bind("jump", KeyCode::Space);
+        actions.clear_bindings("jump");
+        assert!(actions.bindings("jump").is_empty());
+    }
+
+    #[test]
+    fn axis_stays_within_unit_range() {
+        let mut actions = ActionMap::new();
+        actions.bind_axis("horizontal", KeyCode::KeyA, KeyCode::KeyD);
+
+        let axis = |keys: &[KeyCode]| actions.action_axis(&keyboard_holding(keys), "horizontal");
+        assert_eq!(axis(&[]), 0.0);
+        assert_eq!(axis(&[KeyCode::KeyA]), -1.0);
+        assert_eq!(axis(&[KeyCode::KeyD]), 1.0);
+        assert_eq!(axis(&[KeyCode::KeyA, KeyCode::KeyD]), 0.0);
+        assert_eq!(actions.action_axis(&keyboard_holding(&[KeyCode::KeyD]), "vertical"), 0.0);
+    }
+}
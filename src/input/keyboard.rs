@@ -1,57 +1,357 @@
-use winit::keyboard::KeyCode;
-use std::collections::HashSet;
+use winit::keyboard::{Key, KeyCode, NativeKeyCode, PhysicalKey};
+use std::collections::{HashMap, HashSet};
 
+#[derive(Default)]
+struct KeyState {
+    pressed_keys: HashSet<PhysicalKey>,
+    keys_just_pressed: HashSet<PhysicalKey>,
+    keys_just_released: HashSet<PhysicalKey>,
+    // Held keys that received an OS auto-repeat press this frame.
+    keys_repeating: HashSet<PhysicalKey>,
+    // Logical key reported when each physical key went down. Keyed by the
+    // physical key because the logical key can change while it is held
+    // (e.g. releasing Shift before the letter).
+    logical_keys: HashMap<PhysicalKey, Key>,
+    // Logical keys held on keys with no physical identity at all. These
+    // can't go in the maps above without aliasing each other, so they are
+    // tracked by logical key alone, and a release must report the same
+    // logical key as its press.
+    unidentified_logical_keys: HashSet<Key>,
+}
+
+/// Keyboard state, read as a per-frame snapshot.
+///
+/// winit can deliver key events at any point between frames, so events are
+/// collected into a live state and only become visible to the query methods
+/// when [`Keyboard::begin_frame`] is called at the start of a frame. Every
+/// query made during that frame sees the same answer, and events arriving
+/// mid-frame count towards the next one.
+#[derive(Default)]
 pub struct Keyboard {
-    pressed_keys: HashSet<KeyCode>,
-    keys_just_pressed: HashSet<KeyCode>,
-    keys_just_released: HashSet<KeyCode>,
+    live: KeyState,
+    frame: KeyState,
+    // Keys claimed for the current frame by `consume_key`. Cleared by
+    // `begin_frame`.
+    consumed: HashSet<PhysicalKey>,
+    // Seconds each frame-pressed key has been held, as of this frame and
+    // the previous one. Advanced by `begin_frame`.
+    held: HashMap<PhysicalKey, (f32, f32)>,
 }
 
 impl Keyboard {
     pub fn new() -> Self {
-        Self {
-            pressed_keys: HashSet::new(),
-            keys_just_pressed: HashSet::new(),
-            keys_just_released: HashSet::new(),
-        }
+        Self::default()
     }
 
-    pub fn handle_key_event(&mut self, key: KeyCode, is_pressed: bool) {
-        if is_pressed {
-            if !self.pressed_keys.contains(&key) {
-                self.keys_just_pressed.insert(key);
-                self.pressed_keys.insert(key);
+    /// Records a key event. `is_repeat` is winit's `KeyEvent::repeat`;
+    /// repeats never count as a fresh press, only as
+    /// [`Keyboard::key_repeating`].
+    pub fn handle_key_event(
+        &mut self,
+        key: PhysicalKey,
+        logical_key: &Key,
+        is_pressed: bool,
+        is_repeat: bool,
+    ) {
+        let live = &mut self.live;
+        // A fully unidentified key can't be told apart from any other
+        // unidentified key, so only its logical key is tracked.
+        if key == PhysicalKey::Unidentified(NativeKeyCode::Unidentified) {
+            if is_pressed {
+                live.unidentified_logical_keys.insert(logical_key.clone());
+            } else {
+                live.unidentified_logical_keys.remove(logical_key);
             }
-        } else {
-            if self.pressed_keys.contains(&key) {
-                self.keys_just_released.insert(key);
-                self.pressed_keys.remove(&key);
+            return;
+        }
+
+        if is_pressed {
+            if !live.pressed_keys.contains(&key) {
+                live.keys_just_pressed.insert(key);
+                live.pressed_keys.insert(key);
+                live.logical_keys.insert(key, logical_key.clone());
+            } else if is_repeat {
+                live.keys_repeating.insert(key);
             }
+        } else if live.pressed_keys.contains(&key) {
+            live.keys_just_released.insert(key);
+            live.pressed_keys.remove(&key);
+            live.logical_keys.remove(&key);
         }
     }
 
+    /// Publishes the events received since the previous call as this
+    /// frame's state. Call once per frame before reading any input, with
+    /// the real seconds since the last call (used for hold durations).
+    pub fn begin_frame(&mut self, dt: f32) {
+        self.frame.pressed_keys.clone_from(&self.live.pressed_keys);
+        self.frame.logical_keys.clone_from(&self.live.logical_keys);
+        self.frame
+            .unidentified_logical_keys
+            .clone_from(&self.live.unidentified_logical_keys);
+
+        // Swap rather than clone so both sets keep their allocations.
+        std::mem::swap(&mut self.frame.keys_just_pressed, &mut self.live.keys_just_pressed);
+        std::mem::swap(&mut self.frame.keys_just_released, &mut self.live.keys_just_released);
+        std::mem::swap(&mut self.frame.keys_repeating, &mut self.live.keys_repeating);
+        self.live.keys_just_pressed.clear();
+        self.live.keys_just_released.clear();
+        self.live.keys_repeating.clear();
+        self.consumed.clear();
+
+        let frame = &self.frame;
+        self.held.retain(|key, _| frame.pressed_keys.contains(key));
+        for &key in &frame.pressed_keys {
+            let held = self.held.entry(key).or_insert((0.0, 0.0));
+            *held = if frame.keys_just_pressed.contains(&key) {
+                (0.0, 0.0)
+            } else {
+                (held.0 + dt, held.0)
+            };
+        }
+    }
+
+    /// Marks `key` as handled for the rest of this frame, so every later
+    /// query about it returns false until the next [`Keyboard::begin_frame`].
+    ///
+    /// Queries are answered in call order, so whoever should get first
+    /// claim on input (typically UI) must read and consume before gameplay
+    /// code runs.
+    pub fn consume_key(&mut self, key: KeyCode) {
+        self.consumed.insert(PhysicalKey::Code(key));
+    }
+
+    pub fn is_consumed(&self, key: KeyCode) -> bool {
+        self.consumed.contains(&PhysicalKey::Code(key))
+    }
+
     pub fn is_pressed(&self, key: KeyCode) -> bool {
-        self.pressed_keys.contains(&key)
+        self.is_physical_pressed(PhysicalKey::Code(key))
     }
 
     pub fn was_just_pressed(&self, key: KeyCode) -> bool {
-        self.keys_just_pressed.contains(&key)
+        !self.is_consumed(key) && self.frame.keys_just_pressed.contains(&PhysicalKey::Code(key))
     }
 
     pub fn was_just_released(&self, key: KeyCode) -> bool {
-        self.keys_just_released.contains(&key)
+        !self.is_consumed(key) && self.frame.keys_just_released.contains(&PhysicalKey::Code(key))
     }
 
-    pub fn clear_just_pressed(&mut self) {
-        self.keys_just_pressed.clear();
+    /// Whether the OS sent an auto-repeat for this held key this frame.
+    /// Useful for text fields and menu scrolling that should honor the
+    /// platform's repeat rate.
+    pub fn key_repeating(&self, key: KeyCode) -> bool {
+        !self.is_consumed(key) && self.frame.keys_repeating.contains(&PhysicalKey::Code(key))
     }
 
-    pub fn clear_just_released(&mut self) {
-        self.keys_just_released.clear();
+    /// Seconds `key` has been held, 0.0 on the frame it goes down and while
+    /// it is up.
+    pub fn key_held_duration(&self, key: KeyCode) -> f32 {
+        if self.is_consumed(key) {
+            return 0.0;
+        }
+        self.held.get(&PhysicalKey::Code(key)).map_or(0.0, |&(held, _)| held)
     }
 
-    pub fn clear_frame_state(&mut self) {
-        self.keys_just_pressed.clear();
-        self.keys_just_released.clear();
+    /// Hold-to-repeat for menus: true on the frame `key` goes down, then
+    /// once `initial_delay` seconds in, then every `interval` seconds while
+    /// it stays held. Unlike [`Keyboard::key_repeating`] this follows the
+    /// given timings rather than the OS repeat rate.
+    pub fn key_repeat(&self, key: KeyCode, initial_delay: f32, interval: f32) -> bool {
+        if self.was_just_pressed(key) {
+            return true;
+        }
+        if self.is_consumed(key) {
+            return false;
+        }
+        let Some(&(held, previous)) = self.held.get(&PhysicalKey::Code(key)) else {
+            return false;
+        };
+        if interval <= 0.0 {
+            return held >= initial_delay;
+        }
+        // Fire whenever this frame crossed another repeat boundary.
+        let repeats = |time: f32| {
+            if time < initial_delay {
+                0
+            } else {
+                ((time - initial_delay) / interval) as u32 + 1
+            }
+        };
+        repeats(held) > repeats(previous)
+    }
+
+    /// Alias for [`Keyboard::key_repeat`].
+    pub fn key_repeated(&self, key: KeyCode, initial_delay: f32, interval: f32) -> bool {
+        self.key_repeat(key, initial_delay, interval)
+    }
+
+    /// Like [`Keyboard::is_pressed`], but also covers keys that winit only
+    /// knows by their platform scancode.
+    pub fn is_physical_pressed(&self, key: PhysicalKey) -> bool {
+        !self.consumed.contains(&key) && self.frame.pressed_keys.contains(&key)
     }
-}
\ No newline at end of file
+
+    /// Layout-aware check, e.g. `Key::Character("z".into())` follows the
+    /// letter printed on the key rather than its position. Also covers keys
+    /// winit can't identify physically.
+    pub fn is_logical_pressed(&self, key: &Key) -> bool {
+        self.frame.unidentified_logical_keys.contains(key)
+            || self
+                .frame
+                .logical_keys
+                .iter()
+                .any(|(physical, logical)| logical == key && !self.consumed.contains(physical))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use winit::keyboard::NamedKey;
+
+    const W: KeyCode = KeyCode::KeyW;
+
+    fn send(keyboard: &mut Keyboard, key: KeyCode, is_pressed: bool, is_repeat: bool) {
+        let logical = Key::Character("w".into());
+        keyboard.handle_key_event(PhysicalKey::Code(key), &logical, is_pressed, is_repeat);
+    }
+
+    fn press(keyboard: &mut Keyboard, key: KeyCode) {
+        send(keyboard, key, true, false);
+    }
+
+    fn release(keyboard: &mut Keyboard, key: KeyCode) {
+        send(keyboard, key, false, false);
+    }
+
+    /// Holds `W` for `frames` frames of `dt` after the press frame, and
+    /// returns the frames (1-based) on which `repeat` fired.
+    fn repeat_frames(frames: u32, dt: f32, repeat: impl Fn(&Keyboard) -> bool) -> Vec<u32> {
+        let mut keyboard = Keyboard::new();
+        press(&mut keyboard, W);
+        keyboard.begin_frame(dt);
+        assert_eq!(keyboard.key_held_duration(W), 0.0);
+        assert!(repeat(&keyboard), "repeat should fire on the press frame");
+
+        (1..=frames)
+            .filter(|_| {
+                keyboard.begin_frame(dt);
+                repeat(&keyboard)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn key_repeat_fires_at_initial_delay_then_every_interval() {
+        // 0.25s steps: the delay is reached on frame 2, then every 0.5s.
+        let fired = repeat_frames(8, 0.25, |keyboard| keyboard.key_repeat(W, 0.5, 0.5));
+        assert_eq!(fired, vec![2, 4, 6, 8]);
+
+        // Boundaries falling between frames fire once, on the frame that
+        // crosses them.
+        let fired = repeat_frames(10, 0.1, |keyboard| keyboard.key_repeat(W, 0.45, 0.2));
+        assert_eq!(fired, vec![5, 7, 9]);
+    }
+
+    #[test]
+    fn key_held_duration_accumulates_and_resets_on_release() {
+        let mut keyboard = Keyboard::new();
+        press(&mut keyboard, W);
+        keyboard.begin_frame(0.5);
+        assert_eq!(keyboard.key_held_duration(W), 0.0);
+        keyboard.begin_frame(0.25);
+        keyboard.begin_frame(0.25);
+        assert_eq!(keyboard.key_held_duration(W), 0.5);
+
+        release(&mut keyboard, W);
+        keyboard.begin_frame(0.25);
+        assert_eq!(keyboard.key_held_duration(W), 0.0);
+        assert!(!keyboard.key_repeat(W, 0.0, 0.1));
+    }
+
+    #[test]
+    fn key_repeated_fires_like_key_repeat_while_held() {
+        let fired = repeat_frames(8, 0.25, |keyboard| keyboard.key_repeated(W, 0.5, 0.5));
+        assert_eq!(fired, vec![2, 4, 6, 8]);
+    }
+
+    #[test]
+    fn events_after_begin_frame_wait_for_the_next_frame() {
+        let mut keyboard = Keyboard::new();
+        press(&mut keyboard, W);
+        assert!(!keyboard.is_pressed(W), "nothing is visible before begin_frame");
+
+        keyboard.begin_frame(0.0);
+        assert!(keyboard.is_pressed(W) && keyboard.was_just_pressed(W));
+
+        // Mid-frame events leave this frame's answers alone.
+        release(&mut keyboard, W);
+        press(&mut keyboard, KeyCode::KeyA);
+        assert!(keyboard.is_pressed(W) && keyboard.was_just_pressed(W));
+        assert!(!keyboard.was_just_released(W));
+        assert!(!keyboard.is_pressed(KeyCode::KeyA));
+
+        keyboard.begin_frame(0.0);
+        assert!(!keyboard.is_pressed(W) && keyboard.was_just_released(W));
+        assert!(!keyboard.was_just_pressed(W));
+        assert!(keyboard.is_pressed(KeyCode::KeyA));
+    }
+
+    #[test]
+    fn consumed_key_reads_as_up_until_the_next_frame() {
+        let mut keyboard = Keyboard::new();
+        press(&mut keyboard, W);
+        keyboard.begin_frame(0.0);
+        assert!(keyboard.is_pressed(W));
+
+        keyboard.consume_key(W);
+        assert!(keyboard.is_consumed(W));
+        assert!(!keyboard.is_pressed(W));
+        assert!(!keyboard.was_just_pressed(W));
+        assert!(!keyboard.is_logical_pressed(&Key::Character("w".into())));
+        assert!(!keyboard.key_repeat(W, 0.0, 0.1));
+
+        keyboard.begin_frame(0.0);
+        assert!(!keyboard.is_consumed(W));
+        assert!(keyboard.is_pressed(W));
+    }
+
+    #[test]
+    fn os_repeat_sets_the_flag_without_a_new_press() {
+        let mut keyboard = Keyboard::new();
+        press(&mut keyboard, W);
+        keyboard.begin_frame(0.0);
+        assert!(keyboard.was_just_pressed(W) && !keyboard.key_repeating(W));
+
+        send(&mut keyboard, W, true, true);
+        send(&mut keyboard, W, true, true);
+        keyboard.begin_frame(0.0);
+        assert!(keyboard.key_repeating(W));
+        assert!(!keyboard.was_just_pressed(W));
+        assert!(keyboard.is_pressed(W));
+
+        keyboard.begin_frame(0.0);
+        assert!(!keyboard.key_repeating(W));
+    }
+
+    #[test]
+    fn unidentified_keys_are_tracked_by_logical_key() {
+        let mut keyboard = Keyboard::new();
+        let unidentified = PhysicalKey::Unidentified(NativeKeyCode::Unidentified);
+        let media = Key::Named(NamedKey::MediaPlayPause);
+
+        keyboard.handle_key_event(unidentified, &media, true, false);
+        press(&mut keyboard, W);
+        keyboard.begin_frame(0.0);
+        assert!(keyboard.is_logical_pressed(&media));
+        assert!(keyboard.is_pressed(W) && keyboard.was_just_pressed(W));
+        // Unidentified keys never alias each other physically.
+        assert!(!keyboard.is_physical_pressed(unidentified));
+
+        keyboard.handle_key_event(unidentified, &media, false, false);
+        keyboard.begin_frame(0.0);
+        assert!(!keyboard.is_logical_pressed(&media));
+        assert!(keyboard.is_pressed(W));
+    }
+}
@@ -5,4 +5,5 @@
 //! - input mapping (actions/axes)
 //! - per-frame input events
 
-
+pub mod action;
+pub mod keyboard;
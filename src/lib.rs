@@ -0,0 +1,9 @@
+pub mod assets;
+pub mod core;
+pub mod ecs;
+pub mod input;
+pub mod math;
+pub mod render;
+pub mod scene;
+
+pub use render::{run, run_with_config};
@@ -1,6 +1,4 @@
-mod render;
-
 fn main() -> anyhow::Result<()> {
-    render::run()?;
+    grey_engine::run()?;
     Ok(())
 }
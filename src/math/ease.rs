@@ -0,0 +1,106 @@
+use super::{Color, Vec2};
+use std::f32::consts::TAU;
+
+/// An easing curve: maps progress `t` in `0..=1` to eased progress, with
+/// `0 -> 0` and `1 -> 1`. Back and elastic curves overshoot in between.
+pub type EaseFn = fn(f32) -> f32;
+
+pub fn linear(t: f32) -> f32 {
+    t
+}
+
+pub fn ease_in_quad(t: f32) -> f32 {
+    t * t
+}
+
+pub fn ease_out_quad(t: f32) -> f32 {
+    1.0 - (1.0 - t) * (1.0 - t)
+}
+
+pub fn ease_in_out_quad(t: f32) -> f32 {
+    if t < 0.5 {
+        2.0 * t * t
+    } else {
+        1.0 - (-2.0 * t + 2.0).powi(2) / 2.0
+    }
+}
+
+pub fn ease_out_cubic(t: f32) -> f32 {
+    1.0 - (1.0 - t).powi(3)
+}
+
+/// Overshoots the target slightly before settling.
+pub fn ease_out_back(t: f32) -> f32 {
+    const C1: f32 = 1.70158;
+    const C3: f32 = C1 + 1.0;
+    1.0 + C3 * (t - 1.0).powi(3) + C1 * (t - 1.0).powi(2)
+}
+
+/// Springs past the target and wobbles into place.
+pub fn ease_out_elastic(t: f32) -> f32 {
+    if t <= 0.0 {
+        0.0
+    } else if t >= 1.0 {
+        1.0
+    } else {
+        2f32.powf(-10.0 * t) * ((t * 10.0 - 0.75) * (TAU / 3.0)).sin() + 1.0
+    }
+}
+
+/// Value between `from` and `to` at progress `t`, shaped by `ease`. `t` is
+/// clamped to `0..=1`.
+pub fn tween(from: f32, to: f32, t: f32, ease: EaseFn) -> f32 {
+    from + (to - from) * ease(t.clamp(0.0, 1.0))
+}
+
+/// [`tween`] applied to each component.
+pub fn tween_vec2(from: Vec2, to: Vec2, t: f32, ease: EaseFn) -> Vec2 {
+    from.lerp(to, ease(t.clamp(0.0, 1.0)))
+}
+
+/// [`tween`] applied to each channel.
+pub fn tween_color(from: Color, to: Color, t: f32, ease: EaseFn) -> Color {
+    from.lerp(to, ease(t.clamp(0.0, 1.0)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CURVES: [(&str, EaseFn); 7] = [
+        ("linear", linear),
+        ("ease_in_quad", ease_in_quad),
+        ("ease_out_quad", ease_out_quad),
+        ("ease_in_out_quad", ease_in_out_quad),
+        ("ease_out_cubic", ease_out_cubic),
+        ("ease_out_back", ease_out_back),
+        ("ease_out_elastic", ease_out_elastic),
+    ];
+
+    #[test]
+    fn every_curve_starts_at_from_and_ends_at_to() {
+        for (name, ease) in CURVES {
+            let start = tween(-3.0, 7.0, 0.0, ease);
+            let end = tween(-3.0, 7.0, 1.0, ease);
+            assert!((start + 3.0).abs() <= f32::EPSILON, "{name} starts at {start}");
+            assert!((end - 7.0).abs() <= f32::EPSILON, "{name} ends at {end}");
+        }
+    }
+
+    #[test]
+    fn ease_in_out_quad_is_halfway_at_the_middle() {
+        assert_eq!(ease_in_out_quad(0.5), 0.5);
+    }
+
+    #[test]
+    fn tween_clamps_progress() {
+        for (name, ease) in CURVES {
+            assert_eq!(tween(2.0, 4.0, -1.0, ease), tween(2.0, 4.0, 0.0, ease), "{name}");
+            assert_eq!(tween(2.0, 4.0, 3.0, ease), tween(2.0, 4.0, 1.0, ease), "{name}");
+        }
+        let from = Vec2::ZERO;
+        let to = Vec2::new(4.0, -2.0);
+        assert_eq!(tween_vec2(from, to, 2.0, linear), to);
+        assert_eq!(tween_vec2(from, to, -2.0, linear), from);
+    }
+}
@@ -5,4 +5,986 @@
 //! - transforms (position, rotation, scale)
 //! - collision and geometry helpers
 
+pub mod ease;
 
+use std::f32::consts::{PI, TAU};
+use std::fmt;
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+use std::str::FromStr;
+
+/// Tolerance used by the `*_default` comparison helpers.
+pub const EPSILON: f32 = 1e-5;
+
+/// Whether `a` and `b` differ by at most `eps`.
+pub fn approx(a: f32, b: f32, eps: f32) -> bool {
+    (a - b).abs() <= eps
+}
+
+/// [`approx`] with [`EPSILON`] as the tolerance.
+pub fn approx_default(a: f32, b: f32) -> bool {
+    approx(a, b, EPSILON)
+}
+
+/// Brings an angle in radians into `[-PI, PI]`.
+pub fn wrap_angle(radians: f32) -> f32 {
+    (radians + PI).rem_euclid(TAU) - PI
+}
+
+/// Shortest signed turn from `from` to `to`, in `[-PI, PI]`. Positive is
+/// counter-clockwise.
+pub fn angle_diff(from: f32, to: f32) -> f32 {
+    wrap_angle(to - from)
+}
+
+/// Interpolates between two angles along the shorter arc. The result is
+/// not wrapped, so it moves continuously from `from` as `t` goes 0 to 1.
+pub fn angle_lerp(from: f32, to: f32, t: f32) -> f32 {
+    from + angle_diff(from, to) * t
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Vec2 {
+    pub x: f32,
+    pub y: f32,
+}
+
+impl Vec2 {
+    pub const ZERO: Self = Self::new(0.0, 0.0);
+    pub const ONE: Self = Self::new(1.0, 1.0);
+    pub const RIGHT: Self = Self::new(1.0, 0.0);
+    pub const LEFT: Self = Self::new(-1.0, 0.0);
+    pub const UP: Self = Self::new(0.0, 1.0);
+    pub const DOWN: Self = Self::new(0.0, -1.0);
+
+    pub const fn new(x: f32, y: f32) -> Self {
+        Self { x, y }
+    }
+
+    pub const fn splat(value: f32) -> Self {
+        Self::new(value, value)
+    }
+
+    /// Unit vector pointing `radians` counter-clockwise from [`Vec2::RIGHT`].
+    pub fn from_angle(radians: f32) -> Self {
+        let (sin, cos) = radians.sin_cos();
+        Self::new(cos, sin)
+    }
+
+    pub fn dot(&self, other: Vec2) -> f32 {
+        self.x * other.x + self.y * other.y
+    }
+
+    pub fn length(&self) -> f32 {
+        self.dot(*self).sqrt()
+    }
+
+    /// Linear interpolation: `self` at `t = 0`, `other` at `t = 1`.
+    pub fn lerp(&self, other: Vec2, t: f32) -> Vec2 {
+        *self + (other - *self) * t
+    }
+
+    /// Angle from [`Vec2::RIGHT`] in `[-PI, PI]`.
+    pub fn angle(&self) -> f32 {
+        self.y.atan2(self.x)
+    }
+
+    /// Rotates counter-clockwise by `radians`.
+    pub fn rotate(&self, radians: f32) -> Vec2 {
+        let (sin, cos) = radians.sin_cos();
+        Vec2::new(self.x * cos - self.y * sin, self.x * sin + self.y * cos)
+    }
+
+    /// This vector rotated 90 degrees counter-clockwise.
+    pub fn perp(&self) -> Vec2 {
+        Vec2::new(-self.y, self.x)
+    }
+
+    /// Unsigned angle between the two vectors, in `[0, PI]`.
+    pub fn angle_between(&self, other: Vec2) -> f32 {
+        self.cross(other).abs().atan2(self.dot(other))
+    }
+
+    /// Scalar 2D cross product `x * other.y - y * other.x`: positive when
+    /// `other` is counter-clockwise from `self`, zero when parallel.
+    pub fn cross(&self, other: Vec2) -> f32 {
+        self.x * other.y - self.y * other.x
+    }
+
+    /// Alias for [`Vec2::cross`].
+    pub fn perp_dot(&self, other: Vec2) -> f32 {
+        self.cross(other)
+    }
+
+    /// Angle to rotate `self` by to face `other`, in `[-PI, PI]`; positive
+    /// is counter-clockwise.
+    pub fn signed_angle_to(&self, other: Vec2) -> f32 {
+        self.cross(other).atan2(self.dot(other))
+    }
+
+    pub fn min(&self, other: Vec2) -> Vec2 {
+        Vec2::new(self.x.min(other.x), self.y.min(other.y))
+    }
+
+    pub fn max(&self, other: Vec2) -> Vec2 {
+        Vec2::new(self.x.max(other.x), self.y.max(other.y))
+    }
+
+    /// Componentwise clamp. Never panics: where `min` is greater than `max`
+    /// on some axis, that component comes out as `max`.
+    pub fn clamp(&self, min: Vec2, max: Vec2) -> Vec2 {
+        self.max(min).min(max)
+    }
+
+    pub fn abs(&self) -> Vec2 {
+        Vec2::new(self.x.abs(), self.y.abs())
+    }
+
+    pub fn floor(&self) -> Vec2 {
+        Vec2::new(self.x.floor(), self.y.floor())
+    }
+
+    /// Rounds each component, with halfway cases away from zero.
+    pub fn round(&self) -> Vec2 {
+        Vec2::new(self.x.round(), self.y.round())
+    }
+
+    pub fn ceil(&self) -> Vec2 {
+        Vec2::new(self.x.ceil(), self.y.ceil())
+    }
+
+    /// Whether every component is within `eps` of `other`'s.
+    pub fn approx_eq(&self, other: Vec2, eps: f32) -> bool {
+        approx(self.x, other.x, eps) && approx(self.y, other.y, eps)
+    }
+
+    pub fn approx_eq_default(&self, other: Vec2) -> bool {
+        self.approx_eq(other, EPSILON)
+    }
+}
+
+impl Add for Vec2 {
+    type Output = Vec2;
+
+    fn add(self, rhs: Vec2) -> Vec2 {
+        Vec2::new(self.x + rhs.x, self.y + rhs.y)
+    }
+}
+
+impl AddAssign for Vec2 {
+    fn add_assign(&mut self, rhs: Vec2) {
+        *self = *self + rhs;
+    }
+}
+
+impl Sub for Vec2 {
+    type Output = Vec2;
+
+    fn sub(self, rhs: Vec2) -> Vec2 {
+        Vec2::new(self.x - rhs.x, self.y - rhs.y)
+    }
+}
+
+impl SubAssign for Vec2 {
+    fn sub_assign(&mut self, rhs: Vec2) {
+        *self = *self - rhs;
+    }
+}
+
+impl Mul<f32> for Vec2 {
+    type Output = Vec2;
+
+    fn mul(self, rhs: f32) -> Vec2 {
+        Vec2::new(self.x * rhs, self.y * rhs)
+    }
+}
+
+impl Neg for Vec2 {
+    type Output = Vec2;
+
+    fn neg(self) -> Vec2 {
+        Vec2::new(-self.x, -self.y)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Vec3 {
+    pub x: f32,
+    pub y: f32,
+    pub z: f32,
+}
+
+impl Vec3 {
+    pub const ZERO: Self = Self::new(0.0, 0.0, 0.0);
+    pub const ONE: Self = Self::new(1.0, 1.0, 1.0);
+
+    pub const fn new(x: f32, y: f32, z: f32) -> Self {
+        Self { x, y, z }
+    }
+
+    pub fn min(&self, other: Vec3) -> Vec3 {
+        Vec3::new(self.x.min(other.x), self.y.min(other.y), self.z.min(other.z))
+    }
+
+    pub fn max(&self, other: Vec3) -> Vec3 {
+        Vec3::new(self.x.max(other.x), self.y.max(other.y), self.z.max(other.z))
+    }
+
+    /// Componentwise clamp. Never panics: where `min` is greater than `max`
+    /// on some axis, that component comes out as `max`.
+    pub fn clamp(&self, min: Vec3, max: Vec3) -> Vec3 {
+        self.max(min).min(max)
+    }
+
+    pub fn abs(&self) -> Vec3 {
+        Vec3::new(self.x.abs(), self.y.abs(), self.z.abs())
+    }
+
+    pub fn floor(&self) -> Vec3 {
+        Vec3::new(self.x.floor(), self.y.floor(), self.z.floor())
+    }
+
+    /// Rounds each component, with halfway cases away from zero.
+    pub fn round(&self) -> Vec3 {
+        Vec3::new(self.x.round(), self.y.round(), self.z.round())
+    }
+
+    pub fn ceil(&self) -> Vec3 {
+        Vec3::new(self.x.ceil(), self.y.ceil(), self.z.ceil())
+    }
+
+    /// Whether every component is within `eps` of `other`'s.
+    pub fn approx_eq(&self, other: Vec3, eps: f32) -> bool {
+        approx(self.x, other.x, eps) && approx(self.y, other.y, eps) && approx(self.z, other.z, eps)
+    }
+
+    pub fn approx_eq_default(&self, other: Vec3) -> bool {
+        self.approx_eq(other, EPSILON)
+    }
+}
+
+/// 4x4 matrix stored column-major, the same layout WGSL uses for
+/// `mat4x4<f32>`, so `cols` can be copied into a uniform buffer as-is.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Mat4 {
+    pub cols: [[f32; 4]; 4],
+}
+
+impl Mat4 {
+    pub const IDENTITY: Self = Self {
+        cols: [
+            [1.0, 0.0, 0.0, 0.0],
+            [0.0, 1.0, 0.0, 0.0],
+            [0.0, 0.0, 1.0, 0.0],
+            [0.0, 0.0, 0.0, 1.0],
+        ],
+    };
+
+    pub const fn from_cols(cols: [[f32; 4]; 4]) -> Self {
+        Self { cols }
+    }
+
+    pub fn from_translation(translation: Vec3) -> Self {
+        let mut m = Self::IDENTITY;
+        m.cols[3] = [translation.x, translation.y, translation.z, 1.0];
+        m
+    }
+
+    pub fn from_scale(scale: Vec3) -> Self {
+        let mut m = Self::IDENTITY;
+        m.cols[0][0] = scale.x;
+        m.cols[1][1] = scale.y;
+        m.cols[2][2] = scale.z;
+        m
+    }
+
+    /// Counter-clockwise rotation about the Z axis, the only rotation a 2D
+    /// scene needs.
+    pub fn from_rotation_z(radians: f32) -> Self {
+        let (sin, cos) = radians.sin_cos();
+        let mut m = Self::IDENTITY;
+        m.cols[0][0] = cos;
+        m.cols[0][1] = sin;
+        m.cols[1][0] = -sin;
+        m.cols[1][1] = cos;
+        m
+    }
+
+    pub fn transpose(&self) -> Self {
+        let c = &self.cols;
+        let mut m = [[0.0; 4]; 4];
+        for (i, col) in m.iter_mut().enumerate() {
+            for (j, value) in col.iter_mut().enumerate() {
+                *value = c[j][i];
+            }
+        }
+        Self { cols: m }
+    }
+
+    pub fn determinant(&self) -> f32 {
+        let (s, c) = self.sub_determinants();
+        s[0] * c[5] - s[1] * c[4] + s[2] * c[3] + s[3] * c[2] - s[4] * c[1] + s[5] * c[0]
+    }
+
+    /// Inverse by cofactor expansion, or `None` if the matrix is singular
+    /// (for example a zero scale on any axis).
+    pub fn inverse(&self) -> Option<Self> {
+        let m = &self.cols;
+        let (s, c) = self.sub_determinants();
+        let det = s[0] * c[5] - s[1] * c[4] + s[2] * c[3] + s[3] * c[2] - s[4] * c[1] + s[5] * c[0];
+        if det == 0.0 || !det.is_finite() {
+            return None;
+        }
+        let inv_det = 1.0 / det;
+
+        let cols = [
+            [
+                (m[1][1] * c[5] - m[1][2] * c[4] + m[1][3] * c[3]) * inv_det,
+                (-m[0][1] * c[5] + m[0][2] * c[4] - m[0][3] * c[3]) * inv_det,
+                (m[3][1] * s[5] - m[3][2] * s[4] + m[3][3] * s[3]) * inv_det,
+                (-m[2][1] * s[5] + m[2][2] * s[4] - m[2][3] * s[3]) * inv_det,
+            ],
+            [
+                (-m[1][0] * c[5] + m[1][2] * c[2] - m[1][3] * c[1]) * inv_det,
+                (m[0][0] * c[5] - m[0][2] * c[2] + m[0][3] * c[1]) * inv_det,
+                (-m[3][0] * s[5] + m[3][2] * s[2] - m[3][3] * s[1]) * inv_det,
+                (m[2][0] * s[5] - m[2][2] * s[2] + m[2][3] * s[1]) * inv_det,
+            ],
+            [
+                (m[1][0] * c[4] - m[1][1] * c[2] + m[1][3] * c[0]) * inv_det,
+                (-m[0][0] * c[4] + m[0][1] * c[2] - m[0][3] * c[0]) * inv_det,
+                (m[3][0] * s[4] - m[3][1] * s[2] + m[3][3] * s[0]) * inv_det,
+                (-m[2][0] * s[4] + m[2][1] * s[2] - m[2][3] * s[0]) * inv_det,
+            ],
+            [
+                (-m[1][0] * c[3] + m[1][1] * c[1] - m[1][2] * c[0]) * inv_det,
+                (m[0][0] * c[3] - m[0][1] * c[1] + m[0][2] * c[0]) * inv_det,
+                (-m[3][0] * s[3] + m[3][1] * s[1] - m[3][2] * s[0]) * inv_det,
+                (m[2][0] * s[3] - m[2][1] * s[1] + m[2][2] * s[0]) * inv_det,
+            ],
+        ];
+        Some(Self { cols })
+    }
+
+    /// 2x2 determinants of the first two columns (`s`) and the last two
+    /// (`c`), shared by `determinant` and `inverse`.
+    fn sub_determinants(&self) -> ([f32; 6], [f32; 6]) {
+        let m = &self.cols;
+        let s = [
+            m[0][0] * m[1][1] - m[1][0] * m[0][1],
+            m[0][0] * m[1][2] - m[1][0] * m[0][2],
+            m[0][0] * m[1][3] - m[1][0] * m[0][3],
+            m[0][1] * m[1][2] - m[1][1] * m[0][2],
+            m[0][1] * m[1][3] - m[1][1] * m[0][3],
+            m[0][2] * m[1][3] - m[1][2] * m[0][3],
+        ];
+        let c = [
+            m[2][0] * m[3][1] - m[3][0] * m[2][1],
+            m[2][0] * m[3][2] - m[3][0] * m[2][2],
+            m[2][0] * m[3][3] - m[3][0] * m[2][3],
+            m[2][1] * m[3][2] - m[3][1] * m[2][2],
+            m[2][1] * m[3][3] - m[3][1] * m[2][3],
+            m[2][2] * m[3][3] - m[3][2] * m[2][3],
+        ];
+        (s, c)
+    }
+}
+
+impl Mul for Mat4 {
+    type Output = Mat4;
+
+    fn mul(self, rhs: Mat4) -> Mat4 {
+        let mut cols = [[0.0; 4]; 4];
+        for (col, rhs_col) in cols.iter_mut().zip(rhs.cols) {
+            for (row, value) in col.iter_mut().enumerate() {
+                *value = (0..4).map(|k| self.cols[k][row] * rhs_col[k]).sum();
+            }
+        }
+        Mat4 { cols }
+    }
+}
+
+/// Axis-aligned rectangle from its minimum corner (`position`) and `size`.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Rect {
+    pub position: Vec2,
+    pub size: Vec2,
+}
+
+impl Rect {
+    pub const fn new(x: f32, y: f32, width: f32, height: f32) -> Self {
+        Self {
+            position: Vec2::new(x, y),
+            size: Vec2::new(width, height),
+        }
+    }
+
+    pub fn from_center(center: Vec2, size: Vec2) -> Self {
+        Self {
+            position: center - size * 0.5,
+            size,
+        }
+    }
+
+    pub fn min(&self) -> Vec2 {
+        self.position
+    }
+
+    pub fn max(&self) -> Vec2 {
+        self.position + self.size
+    }
+
+    pub fn center(&self) -> Vec2 {
+        self.position + self.size * 0.5
+    }
+
+    /// Edges count as inside.
+    pub fn contains(&self, point: Vec2) -> bool {
+        let (min, max) = (self.min(), self.max());
+        point.x >= min.x && point.x <= max.x && point.y >= min.y && point.y <= max.y
+    }
+
+    /// Overlap test where rectangles that only touch along an edge or at a
+    /// corner count as intersecting.
+    pub fn intersects(&self, other: &Rect) -> bool {
+        let (min, max) = (self.min(), self.max());
+        let (other_min, other_max) = (other.min(), other.max());
+        min.x <= other_max.x
+            && other_min.x <= max.x
+            && min.y <= other_max.y
+            && other_min.y <= max.y
+    }
+
+    /// The overlapping area, which has zero width or height for rectangles
+    /// that only touch.
+    pub fn intersection(&self, other: &Rect) -> Option<Rect> {
+        if !self.intersects(other) {
+            return None;
+        }
+        let (min, max) = (self.min(), self.max());
+        let (other_min, other_max) = (other.min(), other.max());
+        let position = Vec2::new(min.x.max(other_min.x), min.y.max(other_min.y));
+        let corner = Vec2::new(max.x.min(other_max.x), max.y.min(other_max.y));
+        Some(Rect {
+            position,
+            size: corner - position,
+        })
+    }
+
+    /// Grows each side by `dx`/`dy`, keeping the center fixed. Negative
+    /// values shrink it; the size never goes below zero.
+    pub fn inflate(&self, dx: f32, dy: f32) -> Self {
+        let size = Vec2::new(
+            (self.size.x + 2.0 * dx).max(0.0),
+            (self.size.y + 2.0 * dy).max(0.0),
+        );
+        Self::from_center(self.center(), size)
+    }
+
+    pub fn offset(&self, delta: Vec2) -> Self {
+        Self {
+            position: self.position + delta,
+            size: self.size,
+        }
+    }
+
+    /// Scales the size about the center. Negative factors collapse it to
+    /// a point.
+    pub fn scaled(&self, factor: f32) -> Self {
+        Self::from_center(self.center(), self.size * factor.max(0.0))
+    }
+
+    /// Returns `point` if it is inside, otherwise the nearest point on the
+    /// edge.
+    pub fn clamp_point(&self, point: Vec2) -> Vec2 {
+        point.clamp(self.min(), self.max())
+    }
+}
+
+/// Position, rotation (radians, counter-clockwise) and scale of a 2D object.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Transform2D {
+    pub position: Vec2,
+    pub rotation: f32,
+    pub scale: Vec2,
+}
+
+impl Transform2D {
+    pub const IDENTITY: Self = Self::new(Vec2::ZERO, 0.0, Vec2::ONE);
+
+    pub const fn new(position: Vec2, rotation: f32, scale: Vec2) -> Self {
+        Self {
+            position,
+            rotation,
+            scale,
+        }
+    }
+
+    /// Blends towards `other`. Position and scale move linearly, while
+    /// rotation turns the short way round, so 170° to -170° passes through
+    /// 180° rather than 0°.
+    pub fn lerp(&self, other: &Transform2D, t: f32) -> Transform2D {
+        Transform2D {
+            position: self.position.lerp(other.position, t),
+            rotation: angle_lerp(self.rotation, other.rotation, t),
+            scale: self.scale.lerp(other.scale, t),
+        }
+    }
+}
+
+impl Default for Transform2D {
+    fn default() -> Self {
+        Self::IDENTITY
+    }
+}
+
+/// RGBA color with components nominally in `0.0..=1.0`.
+///
+/// Named constants use their CSS values, so [`Color::GREEN`] is `#008000`;
+/// pure green is [`Color::LIME`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Color {
+    pub r: f32,
+    pub g: f32,
+    pub b: f32,
+    pub a: f32,
+}
+
+impl Color {
+    pub const WHITE: Self = Self::new(1.0, 1.0, 1.0, 1.0);
+    pub const BLACK: Self = Self::new(0.0, 0.0, 0.0, 1.0);
+    pub const TRANSPARENT: Self = Self::new(0.0, 0.0, 0.0, 0.0);
+    pub const RED: Self = Self::from_hex(0xff0000);
+    pub const GREEN: Self = Self::from_hex(0x008000);
+    pub const LIME: Self = Self::from_hex(0x00ff00);
+    pub const BLUE: Self = Self::from_hex(0x0000ff);
+    pub const YELLOW: Self = Self::from_hex(0xffff00);
+    pub const CYAN: Self = Self::from_hex(0x00ffff);
+    pub const MAGENTA: Self = Self::from_hex(0xff00ff);
+    pub const ORANGE: Self = Self::from_hex(0xffa500);
+    pub const PURPLE: Self = Self::from_hex(0x800080);
+    pub const GRAY: Self = Self::from_hex(0x808080);
+
+    /// Names accepted by [`Color::from_str`], matched case-insensitively.
+    const NAMED: &[(&str, Color)] = &[
+        ("white", Self::WHITE),
+        ("black", Self::BLACK),
+        ("transparent", Self::TRANSPARENT),
+        ("red", Self::RED),
+        ("green", Self::GREEN),
+        ("lime", Self::LIME),
+        ("blue", Self::BLUE),
+        ("yellow", Self::YELLOW),
+        ("cyan", Self::CYAN),
+        ("aqua", Self::CYAN),
+        ("magenta", Self::MAGENTA),
+        ("fuchsia", Self::MAGENTA),
+        ("orange", Self::ORANGE),
+        ("purple", Self::PURPLE),
+        ("gray", Self::GRAY),
+        ("grey", Self::GRAY),
+    ];
+
+    pub const fn new(r: f32, g: f32, b: f32, a: f32) -> Self {
+        Self { r, g, b, a }
+    }
+
+    /// Opaque color.
+    pub const fn rgb(r: f32, g: f32, b: f32) -> Self {
+        Self::new(r, g, b, 1.0)
+    }
+
+    pub const fn from_rgba8(r: u8, g: u8, b: u8, a: u8) -> Self {
+        Self::new(r as f32 / 255.0, g as f32 / 255.0, b as f32 / 255.0, a as f32 / 255.0)
+    }
+
+    /// Channel-wise linear interpolation, alpha included.
+    pub fn lerp(&self, other: Color, t: f32) -> Color {
+        Color::new(
+            self.r + (other.r - self.r) * t,
+            self.g + (other.g - self.g) * t,
+            self.b + (other.b - self.b) * t,
+            self.a + (other.a - self.a) * t,
+        )
+    }
+
+    /// Opaque color from `0xRRGGBB`, e.g. `Color::from_hex(0xff8800)`.
+    pub const fn from_hex(hex: u32) -> Self {
+        Self::from_rgba8((hex >> 16) as u8, (hex >> 8) as u8, hex as u8, 255)
+    }
+
+    /// Whether every channel is within `eps` of `other`'s.
+    pub fn approx_eq(&self, other: Color, eps: f32) -> bool {
+        approx(self.r, other.r, eps)
+            && approx(self.g, other.g, eps)
+            && approx(self.b, other.b, eps)
+            && approx(self.a, other.a, eps)
+    }
+
+    pub fn approx_eq_default(&self, other: Color) -> bool {
+        self.approx_eq(other, EPSILON)
+    }
+}
+
+impl Default for Color {
+    fn default() -> Self {
+        Self::WHITE
+    }
+}
+
+impl FromStr for Color {
+    type Err = ParseColorError;
+
+    /// Parses `#RRGGBB`, `#RRGGBBAA` or a CSS color name such as `red`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        let error = || ParseColorError(s.to_string());
+        let Some(hex) = s.strip_prefix('#') else {
+            return Self::NAMED
+                .iter()
+                .find(|(name, _)| name.eq_ignore_ascii_case(s))
+                .map(|&(_, color)| color)
+                .ok_or_else(error);
+        };
+
+        // from_str_radix would also accept a leading sign.
+        if !hex.bytes().all(|b| b.is_ascii_hexdigit()) {
+            return Err(error());
+        }
+        let value = u32::from_str_radix(hex, 16).map_err(|_| error())?;
+        match hex.len() {
+            6 => Ok(Self::from_hex(value)),
+            8 => {
+                let [r, g, b, a] = value.to_be_bytes();
+                Ok(Self::from_rgba8(r, g, b, a))
+            }
+            _ => Err(error()),
+        }
+    }
+}
+
+/// Returned by [`Color::from_str`] for input that isn't a hex color or a
+/// known name.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseColorError(String);
+
+impl fmt::Display for ParseColorError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid color `{}`", self.0)
+    }
+}
+
+impl std::error::Error for ParseColorError {}
+
+/// Bit pattern of `value` with `-0.0` folded into `0.0` and every NaN into
+/// one canonical NaN, so equal-looking floats hash the same.
+fn canonical_bits(value: f32) -> u32 {
+    if value == 0.0 {
+        0
+    } else if value.is_nan() {
+        f32::NAN.to_bits()
+    } else {
+        value.to_bits()
+    }
+}
+
+macro_rules! hashable {
+    ($(#[$doc:meta])* $name:ident($inner:ty) { $($field:ident),+ }) => {
+        $(#[$doc])*
+        #[derive(Debug, Clone, Copy)]
+        pub struct $name(pub $inner);
+
+        impl PartialEq for $name {
+            fn eq(&self, other: &Self) -> bool {
+                $(canonical_bits(self.0.$field) == canonical_bits(other.0.$field))&&+
+            }
+        }
+
+        impl Eq for $name {}
+
+        impl std::hash::Hash for $name {
+            fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+                $(canonical_bits(self.0.$field).hash(state);)+
+            }
+        }
+
+        impl From<$inner> for $name {
+            fn from(value: $inner) -> Self {
+                Self(value)
+            }
+        }
+    };
+}
+
+hashable!(
+    /// [`Vec2`] compared bit-for-bit so it can key a `HashMap`, e.g. when
+    /// welding vertices. `-0.0` equals `0.0` and all NaNs equal each other.
+    HashableVec2(Vec2) { x, y }
+);
+hashable!(
+    /// [`Vec3`] compared bit-for-bit; see [`HashableVec2`].
+    HashableVec3(Vec3) { x, y, z }
+);
+hashable!(
+    /// [`Color`] compared bit-for-bit, e.g. for palette reduction; see
+    /// [`HashableVec2`].
+    HashableColor(Color) { r, g, b, a }
+);
+
+/// Smallest power of two that is `>= n`, treating 0 as 1. Useful for
+/// padding texture sizes. Like [`u32::next_power_of_two`], panics in debug
+/// builds if the result would exceed `u32::MAX`.
+pub fn next_pow2(n: u32) -> u32 {
+    n.max(1).next_power_of_two()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn mat4_approx_eq(a: Mat4, b: Mat4, eps: f32) -> bool {
+        a.cols
+            .iter()
+            .flatten()
+            .zip(b.cols.iter().flatten())
+            .all(|(&x, &y)| approx(x, y, eps))
+    }
+
+    #[test]
+    fn mat4_inverse_undoes_translation_rotation_scale() {
+        let m = Mat4::from_translation(Vec3::new(3.0, -2.0, 5.0))
+            * Mat4::from_rotation_z(0.7)
+            * Mat4::from_scale(Vec3::new(2.0, 0.5, 4.0));
+        let inverse = m.inverse().unwrap();
+
+        assert!(mat4_approx_eq(m * inverse, Mat4::IDENTITY, 1e-5));
+        assert!(mat4_approx_eq(inverse * m, Mat4::IDENTITY, 1e-5));
+        assert!(approx(m.determinant(), 4.0, 1e-5));
+    }
+
+    #[test]
+    fn mat4_inverse_of_zero_scale_is_none() {
+        let m = Mat4::from_translation(Vec3::new(1.0, 2.0, 3.0))
+            * Mat4::from_scale(Vec3::new(1.0, 0.0, 1.0));
+        assert_eq!(m.determinant(), 0.0);
+        assert_eq!(m.inverse(), None);
+    }
+
+    #[test]
+    fn mat4_transpose_swaps_rows_and_columns() {
+        let m = Mat4::from_translation(Vec3::new(1.0, 2.0, 3.0));
+        let t = m.transpose();
+        assert_eq!(t.cols[0][3], 1.0);
+        assert_eq!(t.cols[3][0], 0.0);
+        assert_eq!(t.transpose(), m);
+    }
+
+    #[test]
+    fn color_parses_hex_and_names() {
+        assert_eq!("#ff0000".parse::<Color>(), Ok(Color::RED));
+        assert_eq!("#FF0000".parse::<Color>(), Ok(Color::RED));
+        assert_eq!("#ff000080".parse::<Color>(), Ok(Color::from_rgba8(255, 0, 0, 128)));
+        assert_eq!("green".parse::<Color>(), Ok(Color::from_rgba8(0, 128, 0, 255)));
+        assert_eq!(" Grey ".parse::<Color>(), Ok(Color::GRAY));
+    }
+
+    #[test]
+    fn color_rejects_invalid_strings() {
+        for input in ["", "#", "#ff00", "#ff00000", "#+ff0000", "#gg0000", "blurple", "ff0000"] {
+            assert!(input.parse::<Color>().is_err(), "{input:?} should not parse");
+        }
+        let error = "nope".parse::<Color>().unwrap_err();
+        assert_eq!(error.to_string(), "invalid color `nope`");
+    }
+
+    #[test]
+    fn transform_lerp_turns_the_short_way() {
+        let from = Transform2D::new(Vec2::ZERO, 170f32.to_radians(), Vec2::ONE);
+        let to = Transform2D::new(Vec2::new(4.0, -2.0), (-170f32).to_radians(), Vec2::splat(3.0));
+        let mid = from.lerp(&to, 0.5);
+
+        assert!(approx(mid.rotation.abs(), PI, 1e-5), "{}", mid.rotation);
+        assert!(!approx(mid.rotation, 0.0, 0.1));
+        assert_eq!(mid.position, Vec2::new(2.0, -1.0));
+        assert_eq!(mid.scale, Vec2::splat(2.0));
+    }
+
+    #[test]
+    fn angle_diff_takes_the_short_way_across_pi() {
+        let diff = angle_diff(3.0, -3.0);
+        assert!(approx(diff, TAU - 6.0, 1e-5), "{diff}");
+        assert!(approx(angle_diff(-3.0, 3.0), -diff, 1e-5));
+    }
+
+    #[test]
+    fn wrap_angle_stays_within_pi() {
+        for angle in [4.0, -4.0, 10.0 * TAU + 1.0, PI, -PI, 0.0] {
+            let wrapped = wrap_angle(angle);
+            assert!((-PI..=PI).contains(&wrapped), "{angle} wrapped to {wrapped}");
+            assert!(approx(wrapped.sin(), angle.sin(), 1e-4));
+            assert!(approx(wrapped.cos(), angle.cos(), 1e-4));
+        }
+        assert!(approx(wrap_angle(4.0), 4.0 - TAU, 1e-6));
+    }
+
+    #[test]
+    fn angle_lerp_crosses_pi_on_the_short_arc() {
+        let from = PI - 0.1;
+        let to = -PI + 0.1;
+        for t in [0.25, 0.5, 0.75] {
+            let angle = angle_lerp(from, to, t);
+            assert!(angle >= from && angle <= from + 0.2, "t = {t}: {angle}");
+        }
+        assert!(approx(angle_lerp(from, to, 0.5), PI, 1e-5));
+        assert!(approx(wrap_angle(angle_lerp(from, to, 1.0)), to, 1e-5));
+    }
+
+    #[test]
+    fn hashable_wrappers_hash_equal_values_equally() {
+        use std::collections::HashMap;
+        use std::hash::{BuildHasher, RandomState};
+
+        let hasher = RandomState::new();
+        let a = HashableVec3(Vec3::new(1.0, 2.0, 3.0));
+        let b = HashableVec3(Vec3::new(1.0, 2.0, 3.0));
+        assert_eq!(a, b);
+        assert_eq!(hasher.hash_one(a), hasher.hash_one(b));
+
+        let negative_zero = HashableVec2(Vec2::new(-0.0, 0.0));
+        assert_eq!(negative_zero, HashableVec2(Vec2::ZERO));
+        assert_eq!(hasher.hash_one(negative_zero), hasher.hash_one(HashableVec2(Vec2::ZERO)));
+        assert_ne!(HashableVec2(Vec2::ONE), HashableVec2(Vec2::ZERO));
+
+        let mut palette = HashMap::new();
+        for color in [Color::RED, Color::BLUE, Color::RED, Color::from_hex(0xff0000)] {
+            *palette.entry(HashableColor(color)).or_insert(0) += 1;
+        }
+        assert_eq!(palette.len(), 2);
+        assert_eq!(palette[&HashableColor(Color::RED)], 3);
+
+        let welded: HashMap<_, _> = [Vec2::ZERO, Vec2::new(-0.0, -0.0), Vec2::ONE]
+            .into_iter()
+            .map(|v| (HashableVec2(v), v))
+            .collect();
+        assert_eq!(welded.len(), 2);
+    }
+
+    #[test]
+    fn next_pow2_edge_cases() {
+        assert_eq!(next_pow2(0), 1);
+        assert_eq!(next_pow2(1), 1);
+        assert_eq!(next_pow2(100), 128);
+        assert_eq!(next_pow2(128), 128);
+    }
+
+    #[test]
+    fn approx_eq_compares_within_eps() {
+        assert!(approx(1.0, 1.0 + 1e-6, EPSILON));
+        assert!(approx_default(0.1 + 0.2, 0.3));
+        assert!(!approx_default(1.0, 1.1));
+
+        let v2 = Vec2::new(1.0, -2.0);
+        assert!(v2.approx_eq_default(Vec2::new(1.000_001, -2.000_001)));
+        assert!(!v2.approx_eq_default(Vec2::new(1.0, -2.1)));
+        assert!(v2.approx_eq(Vec2::new(1.05, -2.05), 0.1));
+
+        let v3 = Vec3::new(1.0, 2.0, 3.0);
+        assert!(v3.approx_eq_default(Vec3::new(1.0, 2.000_001, 3.0)));
+        assert!(!v3.approx_eq_default(Vec3::new(1.0, 2.0, 3.5)));
+
+        let color = Color::new(0.2, 0.4, 0.6, 1.0);
+        assert!(color.approx_eq_default(Color::new(0.2, 0.4 + 1e-6, 0.6, 1.0)));
+        assert!(!color.approx_eq_default(Color::new(0.2, 0.4, 0.6, 0.5)));
+    }
+
+    #[test]
+    fn rect_inflate_grows_symmetrically() {
+        let rect = Rect::new(10.0, 20.0, 4.0, 6.0);
+        let grown = rect.inflate(1.0, 2.0);
+        assert_eq!(grown, Rect::new(9.0, 18.0, 6.0, 10.0));
+        assert_eq!(grown.center(), rect.center());
+
+        let collapsed = rect.inflate(-3.0, -1.0);
+        assert_eq!(collapsed.size, Vec2::new(0.0, 4.0));
+        assert_eq!(collapsed.center(), rect.center());
+    }
+
+    #[test]
+    fn rect_offset_and_scaled() {
+        let rect = Rect::new(1.0, 2.0, 4.0, 6.0);
+        let moved = rect.offset(Vec2::new(3.0, -1.0));
+        assert_eq!(moved.position, Vec2::new(4.0, 1.0));
+        assert_eq!(moved.size, rect.size);
+
+        assert_eq!(rect.scaled(2.0), Rect::new(-1.0, -1.0, 8.0, 12.0));
+        assert_eq!(rect.scaled(-1.0), Rect::from_center(rect.center(), Vec2::ZERO));
+    }
+
+    #[test]
+    fn rect_clamp_point_snaps_outside_points_to_the_edge() {
+        let rect = Rect::new(0.0, 0.0, 10.0, 5.0);
+        assert_eq!(rect.clamp_point(Vec2::new(3.0, 4.0)), Vec2::new(3.0, 4.0));
+        assert_eq!(rect.clamp_point(Vec2::new(10.0, 0.0)), Vec2::new(10.0, 0.0));
+        assert_eq!(rect.clamp_point(Vec2::new(-2.0, 3.0)), Vec2::new(0.0, 3.0));
+        assert_eq!(rect.clamp_point(Vec2::new(4.0, 9.0)), Vec2::new(4.0, 5.0));
+        assert_eq!(rect.clamp_point(Vec2::new(12.0, -1.0)), Vec2::new(10.0, 0.0));
+    }
+
+    #[test]
+    fn vec2_angles_and_rotation() {
+        assert!(Vec2::from_angle(0.0).approx_eq_default(Vec2::RIGHT));
+        assert!(Vec2::from_angle(PI).approx_eq_default(Vec2::LEFT));
+        assert!(Vec2::RIGHT.rotate(PI / 2.0).approx_eq_default(Vec2::UP));
+        assert!(Vec2::UP.rotate(-PI / 2.0).approx_eq_default(Vec2::RIGHT));
+
+        assert!(approx_default(Vec2::RIGHT.angle_between(Vec2::LEFT), PI));
+        assert!(approx_default(Vec2::new(2.0, 2.0).angle_between(Vec2::new(-1.0, -1.0)), PI));
+        assert!(approx_default(Vec2::RIGHT.angle_between(Vec2::DOWN), PI / 2.0));
+    }
+
+    #[test]
+    fn vec2_cross_and_signed_angle() {
+        assert_eq!(Vec2::RIGHT.cross(Vec2::UP), 1.0);
+        assert_eq!(Vec2::UP.cross(Vec2::RIGHT), -1.0);
+        assert_eq!(Vec2::new(1.0, 2.0).cross(Vec2::new(-2.0, -4.0)), 0.0);
+        assert_eq!(Vec2::RIGHT.perp_dot(Vec2::UP), Vec2::RIGHT.cross(Vec2::UP));
+
+        assert!(approx_default(Vec2::RIGHT.signed_angle_to(Vec2::UP), PI / 2.0));
+        assert!(approx_default(Vec2::RIGHT.signed_angle_to(Vec2::DOWN), -PI / 2.0));
+    }
+
+    #[test]
+    fn rect_intersection_cases() {
+        let rect = Rect::new(0.0, 0.0, 4.0, 4.0);
+
+        let touching = Rect::new(4.0, 1.0, 2.0, 2.0);
+        assert!(rect.intersects(&touching));
+        assert_eq!(rect.intersection(&touching), Some(Rect::new(4.0, 1.0, 0.0, 2.0)));
+
+        let inside = Rect::new(1.0, 1.0, 2.0, 1.0);
+        assert!(rect.intersects(&inside));
+        assert_eq!(rect.intersection(&inside), Some(inside));
+        assert_eq!(inside.intersection(&rect), Some(inside));
+
+        let disjoint = Rect::new(5.0, 0.0, 1.0, 1.0);
+        assert!(!rect.intersects(&disjoint));
+        assert_eq!(rect.intersection(&disjoint), None);
+    }
+
+    #[test]
+    fn vec_clamp_abs_and_round() {
+        let min = Vec2::new(0.0, 5.0);
+        let max = Vec2::new(10.0, 1.0);
+        assert_eq!(Vec2::new(-3.0, 3.0).clamp(min, max), Vec2::new(0.0, 1.0));
+        assert_eq!(Vec2::new(12.0, 7.0).clamp(min, max), Vec2::new(10.0, 1.0));
+        let min = Vec3::new(0.0, 0.0, 2.0);
+        let max = Vec3::new(1.0, 1.0, -2.0);
+        assert_eq!(Vec3::new(0.5, 3.0, 0.0).clamp(min, max), Vec3::new(0.5, 1.0, -2.0));
+
+        assert_eq!(Vec2::new(-1.5, 2.0).abs(), Vec2::new(1.5, 2.0));
+        assert_eq!(Vec3::new(-1.0, -0.0, 3.0).abs(), Vec3::new(1.0, 0.0, 3.0));
+
+        assert_eq!(Vec2::new(0.5, -0.5).round(), Vec2::new(1.0, -1.0));
+        assert_eq!(Vec2::new(2.5, -2.4).round(), Vec2::new(3.0, -2.0));
+        assert_eq!(Vec3::new(1.5, -1.5, 0.49).round(), Vec3::new(2.0, -2.0, 0.0));
+    }
+}
@@ -2,9 +2,9 @@ use std::sync::Arc;
 
 use winit::{
     application::ApplicationHandler,
+    dpi::LogicalSize,
     event::*,
     event_loop::ActiveEventLoop,
-    keyboard::PhysicalKey,
     window::Window,
 };
 
@@ -15,18 +15,24 @@ use winit::event_loop::EventLoop;
 use wasm_bindgen::prelude::*;
 
 use super::state::State;
+use crate::core::config::EngineConfig;
 
 pub struct App {
     #[cfg(target_arch = "wasm32")]
     proxy: Option<winit::event_loop::EventLoopProxy<State>>,
+    config: EngineConfig,
     state: Option<State>,
 }
 
 impl App {
-    pub fn new(#[cfg(target_arch = "wasm32")] event_loop: &EventLoop<State>) -> Self {
+    pub fn new(
+        config: EngineConfig,
+        #[cfg(target_arch = "wasm32")] event_loop: &EventLoop<State>,
+    ) -> Self {
         #[cfg(target_arch = "wasm32")]
         let proxy = Some(event_loop.create_proxy());
         Self {
+            config,
             state: None,
             #[cfg(target_arch = "wasm32")]
             proxy,
@@ -37,7 +43,12 @@ impl App {
 impl ApplicationHandler<State> for App {
     fn resumed(&mut self, event_loop: &ActiveEventLoop) {
         #[allow(unused_mut)]
-        let mut window_attributes = Window::default_attributes().with_title("GreyEngine");
+        let mut window_attributes = Window::default_attributes()
+            .with_title(self.config.window_title.as_str())
+            .with_inner_size(LogicalSize::new(
+                self.config.window_width,
+                self.config.window_height,
+            ));
 
         #[cfg(target_arch = "wasm32")]
         {
@@ -58,7 +69,7 @@ impl ApplicationHandler<State> for App {
         #[cfg(not(target_arch = "wasm32"))]
         {
             // If we are not on web we can use pollster to await
-            let state = pollster::block_on(State::new(window.clone())).unwrap();
+            let state = pollster::block_on(State::new(window.clone(), &self.config)).unwrap();
             window.request_redraw(); // Request initial redraw to start animation loop
             self.state = Some(state);
         }
@@ -69,8 +80,9 @@ impl ApplicationHandler<State> for App {
             // proxy to send the results to the event loop
             if let Some(proxy) = self.proxy.take() {
                 let window_clone = window.clone();
+                let config = self.config.clone();
                 wasm_bindgen_futures::spawn_local(async move {
-                    let state = State::new(window_clone.clone())
+                    let state = State::new(window_clone.clone(), &config)
                         .await
                         .expect("Unable to create canvas!!!");
                     window_clone.request_redraw(); // Request initial redraw
@@ -97,16 +109,9 @@ impl ApplicationHandler<State> for App {
             WindowEvent::RedrawRequested => {
                 state.update();
                 state.render().unwrap();
+                state.end_frame();
             }
-            WindowEvent::KeyboardInput {
-                event:
-                    KeyEvent {
-                        physical_key: PhysicalKey::Code(code),
-                        state: key_state,
-                        ..
-                    },
-                ..
-            } => state.handle_key(event_loop, code, key_state.is_pressed()),
+            WindowEvent::KeyboardInput { event, .. } => state.handle_key(event_loop, &event),
             _ => {}
         }
     }
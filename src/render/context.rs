@@ -1,8 +1,10 @@
 use std::sync::Arc;
 
-use anyhow::Result;
+use anyhow::{Context, Result};
 use winit::window::Window;
 
+use crate::core::config::EngineConfig;
+
 pub struct RenderContext {
     pub surface: wgpu::Surface<'static>,
     pub device: wgpu::Device,
@@ -11,7 +13,7 @@ pub struct RenderContext {
 }
 
 impl RenderContext {
-    pub async fn new(window: Arc<Window>) -> Result<Self> {
+    pub async fn new(window: Arc<Window>, engine_config: &EngineConfig) -> Result<Self> {
         let size = window.inner_size();
 
         // The instance is a handle to our GPU
@@ -34,19 +36,27 @@ impl RenderContext {
             .await?;
 
         let surface_caps = surface.get_capabilities(&adapter);
-        let surface_format = surface_caps
-            .formats
-            .iter()
-            .copied()
-            .find(|f| f.is_srgb())
-            .unwrap_or(surface_caps.formats[0]);
+        let surface_format = select_surface_format(&surface_caps.formats)
+            .context("Surface is not compatible with the adapter: no supported formats")?;
+        if !surface_format.is_srgb() {
+            log::warn!(
+                "No sRGB surface format available, falling back to {:?}; colors will look darker than intended",
+                surface_format
+            );
+        }
+
+        let present_mode = select_present_mode(
+            &engine_config.preferred_present_modes(),
+            &surface_caps.present_modes,
+        );
+        log::info!("Using present mode {:?}", present_mode);
 
         let config = wgpu::SurfaceConfiguration {
             usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
             format: surface_format,
             width: size.width,
             height: size.height,
-            present_mode: surface_caps.present_modes[0],
+            present_mode,
             alpha_mode: surface_caps.alpha_modes[0],
             view_formats: vec![],
             desired_maximum_frame_latency: 2,
@@ -76,6 +86,16 @@ impl RenderContext {
         })
     }
 
+    pub fn surface_format(&self) -> wgpu::TextureFormat {
+        self.config.format
+    }
+
+    /// Whether the surface encodes to sRGB on write. When false, linear shader
+    /// output is shown without gamma encoding and everything looks darker.
+    pub fn is_srgb(&self) -> bool {
+        self.config.format.is_srgb()
+    }
+
     pub fn resize(&mut self, width: u32, height: u32) {
         if width > 0 && height > 0 {
             self.config.width = width;
@@ -85,3 +105,78 @@ impl RenderContext {
     }
 }
 
+/// Picks the first sRGB format the surface supports, falling back to the
+/// surface's preferred format. `None` if it supports no formats at all,
+/// which means it can't be used with the adapter.
+pub fn select_surface_format(formats: &[wgpu::TextureFormat]) -> Option<wgpu::TextureFormat> {
+    formats
+        .iter()
+        .copied()
+        .find(|f| f.is_srgb())
+        .or_else(|| formats.first().copied())
+}
+
+/// Picks the first of `preferred` that the surface supports, falling back
+/// to `Fifo`, which every surface is required to support.
+pub fn select_present_mode(
+    preferred: &[wgpu::PresentMode],
+    supported: &[wgpu::PresentMode],
+) -> wgpu::PresentMode {
+    preferred
+        .iter()
+        .copied()
+        .find(|mode| supported.contains(mode))
+        .unwrap_or(wgpu::PresentMode::Fifo)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::config::EngineConfig;
+    use wgpu::PresentMode::{AutoNoVsync, AutoVsync, Fifo, FifoRelaxed, Immediate, Mailbox};
+    use wgpu::TextureFormat::{Bgra8Unorm, Bgra8UnormSrgb, Rgba16Float, Rgba8UnormSrgb};
+
+    #[test]
+    fn surface_format_prefers_srgb() {
+        let formats = [Bgra8Unorm, Rgba16Float, Rgba8UnormSrgb, Bgra8UnormSrgb];
+        assert_eq!(select_surface_format(&formats), Some(Rgba8UnormSrgb));
+    }
+
+    #[test]
+    fn surface_format_falls_back_to_first_without_srgb() {
+        let format = select_surface_format(&[Rgba16Float, Bgra8Unorm]);
+        assert_eq!(format, Some(Rgba16Float));
+        assert!(!format.unwrap().is_srgb());
+        assert_eq!(select_surface_format(&[]), None);
+    }
+
+    #[test]
+    fn present_mode_takes_first_supported_preference() {
+        let preferred = EngineConfig::default().preferred_present_modes();
+        assert_eq!(preferred, vec![Mailbox, Fifo]);
+
+        assert_eq!(select_present_mode(&preferred, &[Fifo, Mailbox, Immediate]), Mailbox);
+        assert_eq!(select_present_mode(&preferred, &[Immediate, Fifo]), Fifo);
+        assert_eq!(select_present_mode(&preferred, &[FifoRelaxed, Fifo]), Fifo);
+    }
+
+    #[test]
+    fn present_mode_only_uses_immediate_when_asked() {
+        let vsync = EngineConfig::default().preferred_present_modes();
+        assert_eq!(select_present_mode(&vsync, &[Immediate]), Fifo);
+
+        let config = EngineConfig {
+            vsync: false,
+            ..EngineConfig::default()
+        };
+        let no_vsync = config.preferred_present_modes();
+        assert_eq!(select_present_mode(&no_vsync, &[Fifo, Mailbox, Immediate]), Immediate);
+        assert_eq!(select_present_mode(&no_vsync, &[Fifo, Mailbox]), Mailbox);
+    }
+
+    #[test]
+    fn present_mode_falls_back_to_fifo() {
+        assert_eq!(select_present_mode(&[], &[Fifo, Mailbox]), Fifo);
+        assert_eq!(select_present_mode(&[AutoVsync, AutoNoVsync], &[Fifo]), Fifo);
+    }
+}
@@ -10,11 +10,18 @@ mod app;
 pub mod context;
 pub mod pipeline;
 pub mod state;
+pub mod viewport;
 
 use anyhow::Result;
 use winit::event_loop::EventLoop;
 
+use crate::core::config::EngineConfig;
+
 pub fn run() -> Result<()> {
+    run_with_config(EngineConfig::default())
+}
+
+pub fn run_with_config(config: EngineConfig) -> Result<()> {
     #[cfg(not(target_arch = "wasm32"))]
     {
         env_logger::init();
@@ -26,6 +33,7 @@ pub fn run() -> Result<()> {
 
     let event_loop = EventLoop::with_user_event().build()?;
     let mut app = app::App::new(
+        config,
         #[cfg(target_arch = "wasm32")]
         &event_loop,
     );
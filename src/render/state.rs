@@ -1,13 +1,26 @@
-use std::{sync::Arc, time::SystemTime};
+use std::sync::Arc;
 
 use anyhow::Result;
 use winit::{
+    event::KeyEvent,
     event_loop::ActiveEventLoop,
-    keyboard::KeyCode,
+    keyboard::{KeyCode, PhysicalKey},
     window::Window,
 };
 
-use crate::{render::{context::RenderContext, pipeline::create_render_pipeline}};
+use crate::{
+    core::{
+        config::EngineConfig,
+        time::{FrameLimiter, Time},
+    },
+    input::keyboard::Keyboard,
+    math::Rect,
+    render::{
+        context::RenderContext,
+        pipeline::create_render_pipeline,
+        viewport::{clamp_viewport, viewport_aspect},
+    },
+};
 
 pub struct State {
     context: RenderContext,
@@ -16,14 +29,19 @@ pub struct State {
     bind_group: wgpu::BindGroup,
     uniform_buffer: wgpu::Buffer,
     position: [f32; 3],
-    start_time: SystemTime,
+    clear_color: wgpu::Color,
+    // Sub-region of the surface to draw into, in framebuffer pixels. None
+    // draws to the whole surface.
+    viewport: Option<Rect>,
+    time: Time,
+    frame_limiter: FrameLimiter,
     keyboard: Keyboard,
     window: Arc<Window>,
 }
 
 impl State {
-    pub async fn new(window: Arc<Window>) -> Result<Self> {
-        let context = RenderContext::new(window.clone()).await?;
+    pub async fn new(window: Arc<Window>, config: &EngineConfig) -> Result<Self> {
+        let context = RenderContext::new(window.clone(), config).await?;
 
         // vec3<f32> in WGSL uniform buffers is aligned to 16 bytes (like vec4)
         let uniform_buffer = context.device.create_buffer(&wgpu::BufferDescriptor {
@@ -57,7 +75,8 @@ impl State {
         });
         let render_pipeline = create_render_pipeline(&context.device, &context.config, &bind_group_layout);
         let position = [0.0, 0.0, 0.0];
-        let start_time = SystemTime::now();
+        let mut time = Time::new();
+        time.set_fixed_timestep(config.fixed_timestep, config.max_fixed_steps);
 
         Ok(Self {
             context,
@@ -66,7 +85,10 @@ impl State {
             bind_group,
             uniform_buffer,
             position,
-            start_time,
+            clear_color: config.clear_color,
+            viewport: None,
+            time,
+            frame_limiter: FrameLimiter::new(config.max_fps),
             window,
             keyboard: Keyboard::new(),
         })
@@ -81,11 +103,16 @@ impl State {
         }
     }
 
-    pub fn handle_key(&self, event_loop: &ActiveEventLoop, code: KeyCode, is_pressed: bool) {
-        self.keyboard.handle_key_event(code, is_pressed);
-        match (code, is_pressed) {
-            (KeyCode::Escape, true) => event_loop.exit(),
-            _ => {}
+    pub fn handle_key(&mut self, event_loop: &ActiveEventLoop, event: &KeyEvent) {
+        let is_pressed = event.state.is_pressed();
+        self.keyboard.handle_key_event(
+            event.physical_key,
+            &event.logical_key,
+            is_pressed,
+            event.repeat,
+        );
+        if event.physical_key == PhysicalKey::Code(KeyCode::Escape) && is_pressed {
+            event_loop.exit();
         }
     }
 
@@ -120,12 +147,7 @@ impl State {
                     depth_slice: None,
                     resolve_target: None,
                     ops: wgpu::Operations {
-                        load: wgpu::LoadOp::Clear(wgpu::Color {
-                            r: 0.1,
-                            g: 0.2,
-                            b: 0.3,
-                            a: 1.0,
-                        }),
+                        load: wgpu::LoadOp::Clear(self.clear_color),
                         store: wgpu::StoreOp::Store,
                     },
                 })],
@@ -136,9 +158,21 @@ impl State {
 
             // Use the render pipeline so it is not considered dead code,
             // and draw a simple triangle using the vertex_index trick in the shader.
-            render_pass.set_pipeline(&self.render_pipeline);
-            render_pass.set_bind_group(0, &self.bind_group, &[]);
-            render_pass.draw(0..3, 0..1);
+            // The clear above still covers the whole surface, leaving the
+            // area outside the viewport for whatever draws around it.
+            if let Some(viewport) = self.viewport() {
+                render_pass.set_viewport(
+                    viewport.position.x,
+                    viewport.position.y,
+                    viewport.size.x,
+                    viewport.size.y,
+                    0.0,
+                    1.0,
+                );
+                render_pass.set_pipeline(&self.render_pipeline);
+                render_pass.set_bind_group(0, &self.bind_group, &[]);
+                render_pass.draw(0..3, 0..1);
+            }
         }
 
         // submit will accept anything that implements IntoIter<CommandBuffer>
@@ -147,16 +181,56 @@ impl State {
         Ok(())
     }
 
+    /// Restricts drawing to `viewport`, in framebuffer pixels, e.g. a panel
+    /// inside an editor UI. `None` draws to the whole surface again.
+    pub fn set_viewport(&mut self, viewport: Option<Rect>) {
+        self.viewport = viewport;
+    }
+
+    /// The region the scene is drawn into this frame, clipped to the
+    /// surface, or `None` if the viewport is entirely off screen.
+    pub fn viewport(&self) -> Option<Rect> {
+        let (width, height) = (self.context.config.width, self.context.config.height);
+        let viewport = self
+            .viewport
+            .unwrap_or(Rect::new(0.0, 0.0, width as f32, height as f32));
+        clamp_viewport(viewport, width, height)
+    }
+
+    /// Aspect ratio of [`State::viewport`], for building a projection that
+    /// matches it.
+    pub fn aspect_ratio(&self) -> f32 {
+        self.viewport().map_or(0.0, viewport_aspect)
+    }
+
+    /// Ends the frame, waiting if needed to hold the frame rate cap.
+    pub fn end_frame(&mut self) {
+        self.frame_limiter.wait();
+    }
+
+    pub fn max_fps(&self) -> Option<u32> {
+        self.frame_limiter.max_fps()
+    }
+
+    /// Changes the frame rate cap at runtime; `None` removes it.
+    pub fn set_max_fps(&mut self, max_fps: Option<u32>) {
+        self.frame_limiter.set_max_fps(max_fps);
+    }
+
+    pub fn time(&self) -> &Time {
+        &self.time
+    }
+
     pub fn update(&mut self) {
-        // Calculate time since app started for smooth animation
-        let elapsed = SystemTime::now()
-            .duration_since(self.start_time)
-            .unwrap()
-            .as_secs_f32();
+        self.time.update();
+        self.keyboard.begin_frame(self.time.unscaled_delta());
+
+        // Time since app started, read from the clock for smooth animation
+        let elapsed = self.time.seconds();
 
         // Animate in a circle - position changes smoothly over time
-        self.position[0] = elapsed.sin() * 0.3;
-        self.position[1] = elapsed.cos() * 0.3;
+        self.position[0] = (elapsed.sin() * 0.3) as f32;
+        self.position[1] = (elapsed.cos() * 0.3) as f32;
         self.position[2] = 0.0;
     }
 }
\ No newline at end of file
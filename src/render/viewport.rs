@@ -0,0 +1,50 @@
+use crate::math::Rect;
+
+/// Clips `panel` (in framebuffer pixels) to a `surface_width` x
+/// `surface_height` surface. Returns `None` if nothing of it is left on
+/// screen.
+pub fn clamp_viewport(panel: Rect, surface_width: u32, surface_height: u32) -> Option<Rect> {
+    let surface = Rect::new(0.0, 0.0, surface_width as f32, surface_height as f32);
+    panel
+        .intersection(&surface)
+        .filter(|clipped| clipped.size.x > 0.0 && clipped.size.y > 0.0)
+}
+
+/// Width over height of `viewport`, the aspect ratio a camera needs so the
+/// scene isn't stretched to fit it. Zero for an empty viewport.
+pub fn viewport_aspect(viewport: Rect) -> f32 {
+    if viewport.size.y > 0.0 {
+        viewport.size.x / viewport.size.y
+    } else {
+        0.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn panel_inside_the_surface_is_unchanged() {
+        let panel = Rect::new(100.0, 50.0, 400.0, 200.0);
+        assert_eq!(clamp_viewport(panel, 800, 600), Some(panel));
+        assert_eq!(viewport_aspect(panel), 2.0);
+    }
+
+    #[test]
+    fn panel_off_the_edge_is_clipped() {
+        let panel = Rect::new(600.0, -100.0, 400.0, 300.0);
+        let clipped = clamp_viewport(panel, 800, 600).unwrap();
+        assert_eq!(clipped, Rect::new(600.0, 0.0, 200.0, 200.0));
+        assert_eq!(viewport_aspect(clipped), 1.0);
+    }
+
+    #[test]
+    fn panel_off_screen_is_dropped() {
+        let panel = Rect::new(900.0, 0.0, 100.0, 100.0);
+        assert_eq!(clamp_viewport(panel, 800, 600), None);
+        // Touching the edge leaves nothing to draw either.
+        assert_eq!(clamp_viewport(Rect::new(800.0, 0.0, 100.0, 100.0), 800, 600), None);
+        assert_eq!(viewport_aspect(Rect::new(0.0, 0.0, 100.0, 0.0)), 0.0);
+    }
+}
@@ -5,4 +5,5 @@
 //! - levels, prefabs, and serialization
 //! - high-level game objects built on top of ECS
 
-
+pub mod movement;
+pub mod pathfinding;
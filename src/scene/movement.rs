@@ -0,0 +1,122 @@
+use crate::{
+    ecs::world::World,
+    math::{Transform2D, Vec2},
+};
+
+/// Linear velocity in units per second and angular velocity in radians per
+/// second, counter-clockwise. Applied to an entity's [`Transform2D`] by
+/// [`integrate_velocities`].
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Velocity2D {
+    pub linear: Vec2,
+    pub angular: f32,
+}
+
+impl Velocity2D {
+    pub const fn new(linear: Vec2, angular: f32) -> Self {
+        Self { linear, angular }
+    }
+}
+
+/// Exponential drag on a [`Velocity2D`], per second: after `t` seconds the
+/// velocity is `exp(-damping * t)` times what it was. Zero means no drag.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Damping {
+    pub linear: f32,
+    pub angular: f32,
+}
+
+impl Damping {
+    pub const fn new(linear: f32, angular: f32) -> Self {
+        Self { linear, angular }
+    }
+}
+
+/// Moves every entity that has both a [`Transform2D`] and a [`Velocity2D`]
+/// forward by `dt` seconds. Entities with only one of the two are left
+/// alone. Velocities with a [`Damping`] are damped first, so the move uses
+/// the slowed-down velocity.
+pub fn integrate_velocities(world: &mut World, dt: f32) {
+    for (_, velocity, damping) in world.query2_mut::<Velocity2D, Damping>() {
+        velocity.linear = velocity.linear * (-damping.linear * dt).exp();
+        velocity.angular *= (-damping.angular * dt).exp();
+    }
+    for (_, transform, velocity) in world.query2_mut::<Transform2D, Velocity2D>() {
+        transform.position += velocity.linear * dt;
+        transform.rotation += velocity.angular * dt;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::math::approx;
+
+    #[test]
+    fn integrates_position_and_rotation() {
+        let mut world = World::new();
+        let entity = world.spawn_bundle((
+            Transform2D::new(Vec2::new(1.0, 2.0), 0.5, Vec2::ONE),
+            Velocity2D::new(Vec2::new(4.0, -2.0), 1.0),
+        ));
+
+        integrate_velocities(&mut world, 0.5);
+
+        let transform = world.get::<Transform2D>(entity).unwrap();
+        assert_eq!(transform.position, Vec2::new(3.0, 1.0));
+        assert_eq!(transform.rotation, 1.0);
+        assert_eq!(transform.scale, Vec2::ONE);
+    }
+
+    #[test]
+    fn skips_entities_missing_a_transform() {
+        let mut world = World::new();
+        let velocity_only = world.spawn_bundle((Velocity2D::new(Vec2::ONE, 1.0),));
+        let transform_only = world.spawn_bundle((Transform2D::IDENTITY,));
+
+        integrate_velocities(&mut world, 1.0);
+
+        assert!(!world.has::<Transform2D>(velocity_only));
+        assert_eq!(world.get::<Velocity2D>(velocity_only), Some(&Velocity2D::new(Vec2::ONE, 1.0)));
+        assert_eq!(world.get::<Transform2D>(transform_only), Some(&Transform2D::IDENTITY));
+    }
+
+    #[test]
+    fn damping_decays_velocity_exponentially() {
+        let mut world = World::new();
+        let entity = world.spawn_bundle((
+            Transform2D::IDENTITY,
+            Velocity2D::new(Vec2::new(8.0, 0.0), 2.0),
+            Damping::new(0.5, 1.0),
+        ));
+
+        let dt = 0.1;
+        let mut expected_x = 0.0;
+        for step in 1..=20 {
+            integrate_velocities(&mut world, dt);
+            expected_x += 8.0 * (-0.5 * dt * step as f32).exp() * dt;
+        }
+
+        let velocity = world.get::<Velocity2D>(entity).unwrap();
+        assert!(approx(velocity.linear.x, 8.0 * (-1.0f32).exp(), 1e-4), "{velocity:?}");
+        assert!(approx(velocity.angular, 2.0 * (-2.0f32).exp(), 1e-4), "{velocity:?}");
+        let transform = world.get::<Transform2D>(entity).unwrap();
+        assert!(approx(transform.position.x, expected_x, 1e-4), "{transform:?}");
+        assert!(transform.position.x < 8.0 * 2.0);
+    }
+
+    #[test]
+    fn zero_damping_matches_no_damping() {
+        let mut world = World::new();
+        let velocity = Velocity2D::new(Vec2::new(4.0, -2.0), 1.0);
+        let damped = world.spawn_bundle((Transform2D::IDENTITY, velocity, Damping::default()));
+        let undamped = world.spawn_bundle((Transform2D::IDENTITY, velocity));
+
+        for _ in 0..10 {
+            integrate_velocities(&mut world, 0.25);
+        }
+
+        assert_eq!(world.get::<Velocity2D>(damped), Some(&velocity));
+        assert_eq!(world.get::<Transform2D>(damped), world.get::<Transform2D>(undamped));
+    }
+}
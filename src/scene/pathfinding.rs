@@ -0,0 +1,245 @@
+use std::{cmp::Reverse, collections::BinaryHeap};
+
+const STRAIGHT_COST: u32 = 10;
+const DIAGONAL_COST: u32 = 14;
+
+const STRAIGHT_STEPS: [(i32, i32); 4] = [(1, 0), (-1, 0), (0, 1), (0, -1)];
+const DIAGONAL_STEPS: [(i32, i32); 4] = [(1, 1), (1, -1), (-1, 1), (-1, -1)];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Connectivity {
+    /// Up, down, left and right.
+    Four,
+    /// Also diagonals. A diagonal step is only allowed when both cells it
+    /// squeezes between are walkable, so paths never cut corners.
+    Eight,
+}
+
+/// A* search over a `width` x `height` grid of cells.
+///
+/// The open list and per-cell bookkeeping are kept between searches, so
+/// reusing one `GridPathfinder` avoids allocating once the buffers have
+/// grown to the grid size.
+#[derive(Default)]
+pub struct GridPathfinder {
+    open: BinaryHeap<Reverse<(u32, u32, usize)>>,
+    cost: Vec<u32>,
+    came_from: Vec<usize>,
+    closed: Vec<bool>,
+}
+
+impl GridPathfinder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Finds a shortest path from `start` to `goal`, both included, or
+    /// `None` if the goal can't be reached. Cells outside the grid count as
+    /// blocked; `is_walkable` is only asked about cells inside it.
+    pub fn find_path(
+        &mut self,
+        width: i32,
+        height: i32,
+        start: (i32, i32),
+        goal: (i32, i32),
+        connectivity: Connectivity,
+        is_walkable: impl Fn(i32, i32) -> bool,
+    ) -> Option<Vec<(i32, i32)>> {
+        let in_bounds = |(x, y): (i32, i32)| x >= 0 && y >= 0 && x < width && y < height;
+        let walkable = |cell: (i32, i32)| in_bounds(cell) && is_walkable(cell.0, cell.1);
+        if !in_bounds(start) || !walkable(goal) {
+            return None;
+        }
+
+        let index = |(x, y): (i32, i32)| (y * width + x) as usize;
+        let cell = |index: usize| ((index as i32) % width, (index as i32) / width);
+        let heuristic = |(x, y): (i32, i32)| {
+            let dx = x.abs_diff(goal.0);
+            let dy = y.abs_diff(goal.1);
+            match connectivity {
+                Connectivity::Four => (dx + dy) * STRAIGHT_COST,
+                Connectivity::Eight => {
+                    DIAGONAL_COST * dx.min(dy) + STRAIGHT_COST * dx.abs_diff(dy)
+                }
+            }
+        };
+
+        self.reset((width * height) as usize);
+        let start_index = index(start);
+        let goal_index = index(goal);
+        self.cost[start_index] = 0;
+        self.open.push(Reverse((heuristic(start), 0, start_index)));
+
+        while let Some(Reverse((_, cost, current))) = self.open.pop() {
+            if current == goal_index {
+                return Some(self.build_path(start_index, goal_index, cell));
+            }
+            // Stale heap entry for a cell already expanded more cheaply.
+            if self.closed[current] || cost > self.cost[current] {
+                continue;
+            }
+            self.closed[current] = true;
+
+            let (x, y) = cell(current);
+            let diagonals: &[(i32, i32)] = match connectivity {
+                Connectivity::Four => &[],
+                Connectivity::Eight => &DIAGONAL_STEPS,
+            };
+            let steps = STRAIGHT_STEPS
+                .iter()
+                .map(|&step| (step, STRAIGHT_COST))
+                .chain(diagonals.iter().map(|&step| (step, DIAGONAL_COST)));
+
+            for ((dx, dy), step_cost) in steps {
+                let next = (x + dx, y + dy);
+                if !walkable(next) {
+                    continue;
+                }
+                if dx != 0 && dy != 0 && !(walkable((x + dx, y)) && walkable((x, y + dy))) {
+                    continue;
+                }
+
+                let next_index = index(next);
+                let next_cost = cost + step_cost;
+                if !self.closed[next_index] && next_cost < self.cost[next_index] {
+                    self.cost[next_index] = next_cost;
+                    self.came_from[next_index] = current;
+                    self.open
+                        .push(Reverse((next_cost + heuristic(next), next_cost, next_index)));
+                }
+            }
+        }
+
+        None
+    }
+
+    fn reset(&mut self, cells: usize) {
+        self.open.clear();
+        self.cost.clear();
+        self.cost.resize(cells, u32::MAX);
+        self.came_from.clear();
+        self.came_from.resize(cells, usize::MAX);
+        self.closed.clear();
+        self.closed.resize(cells, false);
+    }
+
+    fn build_path(
+        &self,
+        start: usize,
+        goal: usize,
+        cell: impl Fn(usize) -> (i32, i32),
+    ) -> Vec<(i32, i32)> {
+        let mut path = vec![cell(goal)];
+        let mut current = goal;
+        while current != start {
+            current = self.came_from[current];
+            path.push(cell(current));
+        }
+        path.reverse();
+        path
+    }
+}
+
+/// One-off search with a fresh [`GridPathfinder`]. Prefer keeping a
+/// pathfinder around when searching every frame.
+pub fn find_path(
+    width: i32,
+    height: i32,
+    start: (i32, i32),
+    goal: (i32, i32),
+    connectivity: Connectivity,
+    is_walkable: impl Fn(i32, i32) -> bool,
+) -> Option<Vec<(i32, i32)>> {
+    GridPathfinder::new().find_path(width, height, start, goal, connectivity, is_walkable)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Walkability from rows of `.` (open) and `#` (blocked).
+    fn grid(rows: &[&str]) -> (i32, i32, impl Fn(i32, i32) -> bool) {
+        let cells: Vec<Vec<bool>> = rows
+            .iter()
+            .map(|row| row.chars().map(|c| c == '.').collect())
+            .collect();
+        let (width, height) = (cells[0].len() as i32, cells.len() as i32);
+        (width, height, move |x: i32, y: i32| cells[y as usize][x as usize])
+    }
+
+    #[test]
+    fn straight_open_path() {
+        let (width, height, walkable) = grid(&["....."]);
+        let path = find_path(width, height, (0, 0), (4, 0), Connectivity::Four, walkable);
+        assert_eq!(path, Some(vec![(0, 0), (1, 0), (2, 0), (3, 0), (4, 0)]));
+    }
+
+    #[test]
+    fn path_around_an_obstacle() {
+        let (width, height, walkable) = grid(&[
+            "...",
+            ".#.",
+            "...",
+        ]);
+        let path = find_path(width, height, (0, 1), (2, 1), Connectivity::Four, &walkable).unwrap();
+        assert_eq!(path.len(), 5);
+        assert_eq!(path.first(), Some(&(0, 1)));
+        assert_eq!(path.last(), Some(&(2, 1)));
+        assert!(!path.contains(&(1, 1)));
+        for step in path.windows(2) {
+            let (a, b) = (step[0], step[1]);
+            assert_eq!(a.0.abs_diff(b.0) + a.1.abs_diff(b.1), 1);
+        }
+
+        // Diagonals may not squeeze past the obstacle's corners.
+        let path = find_path(width, height, (0, 0), (2, 2), Connectivity::Eight, &walkable);
+        let path = path.unwrap();
+        assert_eq!(path.len(), 5);
+        assert!(!path.contains(&(1, 1)));
+    }
+
+    #[test]
+    fn unreachable_goal_returns_none() {
+        let (width, height, walkable) = grid(&[
+            "..#..",
+            "..#..",
+            "..#..",
+        ]);
+        assert_eq!(find_path(width, height, (0, 0), (4, 2), Connectivity::Eight, &walkable), None);
+        // Blocked or out-of-bounds goals too.
+        assert_eq!(find_path(width, height, (0, 0), (2, 0), Connectivity::Four, &walkable), None);
+        assert_eq!(find_path(width, height, (0, 0), (9, 0), Connectivity::Four, &walkable), None);
+    }
+
+    #[test]
+    fn one_pathfinder_reused_across_grid_sizes() {
+        let mut pathfinder = GridPathfinder::new();
+        let (big_width, big_height, big) = grid(&[
+            "..........",
+            "########..",
+            "..........",
+        ]);
+        let (small_width, small_height, small) = grid(&["..", ".."]);
+
+        let first = pathfinder
+            .find_path(big_width, big_height, (0, 0), (0, 2), Connectivity::Four, &big)
+            .unwrap();
+        assert_eq!(first.len(), 19);
+
+        let path = pathfinder.find_path(
+            small_width,
+            small_height,
+            (0, 0),
+            (1, 1),
+            Connectivity::Eight,
+            &small,
+        );
+        assert_eq!(path, Some(vec![(0, 0), (1, 1)]));
+
+        // Nothing left over from the smaller search leaks into the bigger one.
+        let again = pathfinder
+            .find_path(big_width, big_height, (0, 0), (0, 2), Connectivity::Four, &big)
+            .unwrap();
+        assert_eq!(again, first);
+    }
+}